@@ -0,0 +1,133 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Steps from `initial` until a state repeats and returns `(prefix_len, cycle_len)`: state
+/// `prefix_len` is the first one on the cycle, and it comes back every `cycle_len` steps.
+///
+/// Every state is kept in a map, see `find_cycle_brent` when they are too big for that
+pub fn find_cycle<S>(initial: S, mut step: impl FnMut(&S) -> S) -> (usize, usize)
+where
+    S: Eq + Hash,
+{
+    let mut seen = HashMap::new();
+    let mut state = initial;
+    for i in 0.. {
+        if let Some(&first) = seen.get(&state) {
+            return (first, i - first);
+        }
+        let next = step(&state);
+        seen.insert(state, i);
+        state = next;
+    }
+    unreachable!()
+}
+
+/// Same as `find_cycle` but only ever holds two states, at the cost of calling `step` about
+/// three times as often
+pub fn find_cycle_brent<S>(initial: S, mut step: impl FnMut(&S) -> S) -> (usize, usize)
+where
+    S: Clone + Eq,
+{
+    // Find the cycle length: the hare runs ahead in growing powers of two until it meets the
+    // tortoise parked at the start of the current stretch
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = step(&hare);
+        len += 1;
+    }
+
+    // With the hare `len` steps ahead, both meet at the start of the cycle
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..len {
+        hare = step(&hare);
+    }
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+    (prefix, len)
+}
+
+/// The state after `n` steps from `initial`, skipping whole cycles once one is found. So
+/// `n` can be far beyond what could be simulated, e.g. 1,000,000,000
+pub fn nth_state<S>(initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S
+where
+    S: Clone + Eq + Hash,
+{
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+    for i in 0..n {
+        if let Some(&first) = seen.get(&state) {
+            let len = i - first;
+            return states.swap_remove(first + (n - first) % len);
+        }
+        let next = step(&state);
+        seen.insert(state.clone(), i);
+        states.push(state);
+        state = next;
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, 2, 3, 4, 5, 3, 4, 5, ..
+    fn step(&n: &u32) -> u32 {
+        if n == 5 {
+            3
+        } else {
+            n + 1
+        }
+    }
+
+    #[test]
+    fn cycles() {
+        assert_eq!(find_cycle(0, step), (3, 3));
+        assert_eq!(find_cycle_brent(0, step), (3, 3));
+        assert_eq!(find_cycle(4, step), (0, 3));
+        assert_eq!(find_cycle_brent(4, step), (0, 3));
+
+        // Fixed points are cycles of length 1
+        assert_eq!(find_cycle(7, |_| 8), (1, 1));
+        assert_eq!(find_cycle_brent(7, |_| 8), (1, 1));
+
+        let collatz = |&n: &u64| if n % 2 == 0 { n / 2 } else { 3 * n + 1 };
+        assert_eq!(find_cycle(27, collatz), find_cycle_brent(27, collatz));
+    }
+
+    #[test]
+    fn steps_taken() {
+        // States 0 to 5 are each stepped from once, the repeat of 3 never is
+        let mut calls = 0;
+        find_cycle(0, |n| {
+            calls += 1;
+            step(n)
+        });
+        assert_eq!(calls, 6);
+    }
+
+    #[test]
+    fn nth() {
+        for n in 0..20 {
+            let mut state = 0;
+            for _ in 0..n {
+                state = step(&state);
+            }
+            assert_eq!(nth_state(0, step, n), state, "n = {n}");
+        }
+        assert_eq!(nth_state(0, step, 1_000_000_000), 4);
+    }
+}
@@ -0,0 +1,273 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::grid::Coord;
+
+/// One of the 4 orthogonal directions, north being up
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub enum Direction {
+    N,
+    E,
+    S,
+    W,
+}
+
+impl Direction {
+    /// Clockwise from north
+    pub const ALL: [Direction; 4] = [Direction::N, Direction::E, Direction::S, Direction::W];
+
+    pub fn iter() -> impl DoubleEndedIterator<Item = Direction> {
+        Self::ALL.into_iter()
+    }
+
+    pub const fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+    pub const fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+    pub const fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    pub const fn to_coord(self) -> Coord {
+        Coord::DIRS4[self as usize]
+    }
+    /// The direction of a single orthogonal step
+    pub fn from_coord(c: Coord) -> Option<Self> {
+        Self::iter().find(|d| d.to_coord() == c)
+    }
+
+    /// Accepts `U/D/L/R`, `N/S/E/W` in either case, `^v<>` and `↑↓←→`
+    pub const fn from_char(c: char) -> Option<Self> {
+        Some(match c {
+            'U' | 'u' | 'N' | 'n' | '^' | '↑' => Direction::N,
+            'R' | 'r' | 'E' | 'e' | '>' | '→' => Direction::E,
+            'D' | 'd' | 'S' | 's' | 'v' | 'V' | '↓' => Direction::S,
+            'L' | 'l' | 'W' | 'w' | '<' | '←' => Direction::W,
+            _ => return None,
+        })
+    }
+    /// The arrow pointing this way, as in `^>v<`
+    pub const fn to_char(self) -> char {
+        match self {
+            Direction::N => '^',
+            Direction::E => '>',
+            Direction::S => 'v',
+            Direction::W => '<',
+        }
+    }
+}
+
+/// One of the 8 compass directions, north being up
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub enum Direction8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction8 {
+    /// Clockwise from north
+    pub const ALL: [Direction8; 8] = [
+        Direction8::N,
+        Direction8::NE,
+        Direction8::E,
+        Direction8::SE,
+        Direction8::S,
+        Direction8::SW,
+        Direction8::W,
+        Direction8::NW,
+    ];
+
+    pub fn iter() -> impl DoubleEndedIterator<Item = Direction8> {
+        Self::ALL.into_iter()
+    }
+
+    /// An eighth of a turn anticlockwise
+    pub const fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+    /// An eighth of a turn clockwise
+    pub const fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+    pub const fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    pub const fn to_coord(self) -> Coord {
+        Coord::DIRS8[self as usize]
+    }
+    pub fn from_coord(c: Coord) -> Option<Self> {
+        Self::iter().find(|d| d.to_coord() == c)
+    }
+    /// `None` for the diagonals
+    pub const fn to_orthogonal(self) -> Option<Direction> {
+        match self {
+            Direction8::N => Some(Direction::N),
+            Direction8::E => Some(Direction::E),
+            Direction8::S => Some(Direction::S),
+            Direction8::W => Some(Direction::W),
+            _ => None,
+        }
+    }
+
+    /// Accepts everything `Direction::from_char` does plus the diagonal arrows `↗↘↙↖`
+    pub const fn from_char(c: char) -> Option<Self> {
+        Some(match c {
+            '↗' => Direction8::NE,
+            '↘' => Direction8::SE,
+            '↙' => Direction8::SW,
+            '↖' => Direction8::NW,
+            _ => match Direction::from_char(c) {
+                Some(d) => Direction8::from_orthogonal(d),
+                None => return None,
+            },
+        })
+    }
+    const fn from_orthogonal(d: Direction) -> Self {
+        Self::ALL[d as usize * 2]
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(d: Direction) -> Self {
+        Self::from_orthogonal(d)
+    }
+}
+impl From<Direction> for Coord {
+    fn from(d: Direction) -> Self {
+        d.to_coord()
+    }
+}
+impl From<Direction8> for Coord {
+    fn from(d: Direction8) -> Self {
+        d.to_coord()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ParseDirectionError;
+
+impl Display for ParseDirectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("not a direction")
+    }
+}
+impl std::error::Error for ParseDirectionError {}
+
+impl TryFrom<char> for Direction {
+    type Error = ParseDirectionError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Self::from_char(c).ok_or(ParseDirectionError)
+    }
+}
+impl TryFrom<char> for Direction8 {
+    type Error = ParseDirectionError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Self::from_char(c).ok_or(ParseDirectionError)
+    }
+}
+
+/// A single direction char, or a compass name like `N` or `north`
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return c.try_into();
+        }
+        match s.to_ascii_lowercase().as_str() {
+            "up" | "north" => Ok(Direction::N),
+            "right" | "east" => Ok(Direction::E),
+            "down" | "south" => Ok(Direction::S),
+            "left" | "west" => Ok(Direction::W),
+            _ => Err(ParseDirectionError),
+        }
+    }
+}
+
+/// Anything `Direction` parses, plus `NE/SE/SW/NW` in either case and the diagonal arrows
+impl FromStr for Direction8 {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "NE" => Ok(Direction8::NE),
+            "SE" => Ok(Direction8::SE),
+            "SW" => Ok(Direction8::SW),
+            "NW" => Ok(Direction8::NW),
+            _ => match s.parse::<Direction>() {
+                Ok(d) => Ok(d.into()),
+                Err(e) => {
+                    let mut chars = s.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => c.try_into(),
+                        _ => Err(e),
+                    }
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        use Direction::*;
+        assert_eq!(N.turn_right(), E);
+        assert_eq!(N.turn_left(), W);
+        assert_eq!(W.turn_right(), N);
+        assert_eq!(E.reverse(), W);
+        for d in Direction::iter() {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.to_coord().rotate_cw_90(), d.turn_right().to_coord());
+            assert_eq!(Direction::from_coord(d.to_coord()), Some(d));
+        }
+        assert_eq!(Direction::from_coord(Coord::new(1, 1)), None);
+
+        assert_eq!(Direction8::N.turn_left(), Direction8::NW);
+        assert_eq!(Direction8::SE.reverse(), Direction8::NW);
+        assert_eq!(Direction8::SW.to_coord(), Coord::SW);
+        assert_eq!(Direction8::from(S), Direction8::S);
+        assert_eq!(Direction8::W.to_orthogonal(), Some(W));
+        assert_eq!(Direction8::NE.to_orthogonal(), None);
+        assert_eq!(Direction8::iter().count(), 8);
+    }
+
+    #[test]
+    fn parse() {
+        use Direction::*;
+        let all: Vec<_> = "UDLR NSWE ^v<> ↑↓←→"
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| Direction::try_from(c).unwrap())
+            .collect();
+        assert_eq!(all, [N, S, W, E].repeat(4));
+
+        assert_eq!("R".parse(), Ok(E));
+        assert_eq!("north".parse(), Ok(N));
+        assert_eq!("Left".parse(), Ok(W));
+        assert_eq!("x".parse::<Direction>(), Err(ParseDirectionError));
+        assert_eq!("NE".parse::<Direction>(), Err(ParseDirectionError));
+
+        assert_eq!("ne".parse(), Ok(Direction8::NE));
+        assert_eq!("↙".parse(), Ok(Direction8::SW));
+        assert_eq!("<".parse(), Ok(Direction8::W));
+        assert_eq!(Direction8::from_char('D'), Some(Direction8::S));
+
+        let arrows: String = Direction::iter().map(Direction::to_char).collect();
+        assert_eq!(arrows, "^>v<");
+    }
+}
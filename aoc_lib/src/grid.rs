@@ -1,14 +1,24 @@
 use std::{
     cmp::Ordering,
-    fmt::Debug,
+    fmt::{Debug, Display},
+    iter::Sum,
     marker::PhantomData,
     ops::{
-        Add, AddAssign, Bound, Index, IndexMut, Mul, MulAssign, Range, RangeBounds, Sub, SubAssign,
+        Add, AddAssign, Bound, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Range,
+        RangeBounds, Rem, RemAssign, Sub, SubAssign,
     },
     panic::UnwindSafe,
 };
 
-#[derive(Clone, PartialEq, Eq, Hash)]
+use crate::{
+    render::{self, Overlay},
+    transform::{TransformedColumn, TransformedRow},
+};
+
+#[cfg(feature = "rayon")]
+mod par;
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct Grid<T> {
     rows: usize,
     cols: usize,
@@ -25,11 +35,68 @@ impl<T> Grid<T> {
     pub fn new(rows: usize, cols: usize, data: Vec<T>) -> Self {
         let size = rows * cols;
         assert_eq!(data.len(), size);
-        Self {
-            rows,
-            cols,
-            data: Vec::new(),
+        Self { rows, cols, data }
+    }
+    pub fn filled(rows: usize, cols: usize, v: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(rows, cols, vec![v; rows * cols])
+    }
+    pub fn from_fn(rows: usize, cols: usize, f: impl FnMut(Coord) -> T) -> Self {
+        let data = Coords::new(Coord::ORIGIN, Coord::new(rows as i64, cols as i64))
+            .map(f)
+            .collect();
+        Self::new(rows, cols, data)
+    }
+    /// Parses one cell per char and one row per line.
+    ///
+    /// Blank lines before and after the grid are skipped and indentation shared by every line is
+    /// removed, so indented string literals in tests parse the same as real inputs. A first line
+    /// without any indentation doesn't count, since tests often `trim` the input.
+    #[track_caller]
+    pub fn parse_with(input: &str, mut f: impl FnMut(char) -> T) -> Self {
+        let lines: Vec<_> = input.lines().skip_while(|l| l.trim().is_empty()).collect();
+        let end = lines
+            .iter()
+            .rposition(|l| !l.trim().is_empty())
+            .map_or(0, |i| i + 1);
+        let lines = &lines[..end];
+
+        fn indent_of(l: &str) -> &str {
+            &l[..l.len() - l.trim_start().len()]
+        }
+        let rest = match lines {
+            [first, rest @ ..] if indent_of(first).is_empty() && !rest.is_empty() => rest,
+            _ => lines,
+        };
+        let mut indent = rest.first().copied().map_or("", indent_of);
+        for l in rest {
+            // Longest common prefix, which is always on a char boundary
+            let common = indent
+                .char_indices()
+                .zip(indent_of(l).chars())
+                .find(|((_, a), b)| a != b)
+                .map_or(indent.len().min(indent_of(l).len()), |((i, _), _)| i);
+            indent = &indent[..common];
         }
+
+        let mut grid = Self::build();
+        for l in lines {
+            let l = l.strip_prefix(indent).unwrap_or(l);
+            grid.push_row(l.chars().map(&mut f).collect());
+        }
+        grid.finish()
+    }
+    #[track_caller]
+    pub fn parse_digits(input: &str) -> Self
+    where
+        T: From<u8>,
+    {
+        Self::parse_with(input, |c| match c.to_digit(10) {
+            Some(d) => T::from(d as u8),
+            None => panic!("Expected a digit found {c:?}"),
+        })
     }
     pub const fn build() -> GridBuilder<T> {
         GridBuilder::new()
@@ -47,7 +114,7 @@ impl<T> Grid<T> {
     fn coord_to_index(&self, coord: Coord) -> Option<usize> {
         coord_to_index(coord, self.num_rows(), self.num_cols())
     }
-    fn raw_ref(&self) -> RawGridRef<T> {
+    fn raw_ref(&self) -> RawGridRef<'_, T> {
         let ptr = self.data.as_ptr();
         RawGridRef {
             data: PhantomData,
@@ -56,7 +123,7 @@ impl<T> Grid<T> {
             cols: self.num_cols(),
         }
     }
-    fn raw_mut(&mut self) -> RawGridMut<T> {
+    fn raw_mut(&mut self) -> RawGridMut<'_, T> {
         let ptr = self.data.as_mut_ptr();
         RawGridMut {
             marker: PhantomData,
@@ -73,42 +140,42 @@ impl<T> Grid<T> {
         self.data.get_mut(index)
     }
 
-    pub fn row(&self, index: i64) -> Row<T> {
+    pub fn row(&self, index: i64) -> Row<'_, T> {
         Row::new(self, index)
     }
-    pub fn row_mut(&mut self, index: i64) -> RowMut<T> {
+    pub fn row_mut(&mut self, index: i64) -> RowMut<'_, T> {
         RowMut::new(self, index)
     }
-    pub fn col(&self, index: i64) -> Column<T> {
+    pub fn col(&self, index: i64) -> Column<'_, T> {
         Column::new(self, index)
     }
-    pub fn col_mut(&mut self, index: i64) -> ColumnMut<T> {
+    pub fn col_mut(&mut self, index: i64) -> ColumnMut<'_, T> {
         ColumnMut::new(self, index)
     }
 
-    pub fn rows(&self) -> Rows<T> {
+    pub fn rows(&self) -> Rows<'_, T> {
         Rows {
             grid: self.raw_ref(),
             start: 0,
             end: self.num_rows(),
         }
     }
-    pub fn rows_mut(&mut self) -> RowsMut<T> {
-        let end = self.raw_mut().row_size();
+    pub fn rows_mut(&mut self) -> RowsMut<'_, T> {
+        let end = self.raw_mut().col_size();
         RowsMut {
             grid: self.raw_mut(),
             start: 0,
             end,
         }
     }
-    pub fn cols(&self) -> Columns<T> {
+    pub fn cols(&self) -> Columns<'_, T> {
         Columns {
             grid: self.raw_ref(),
             start: 0,
             end: self.num_cols(),
         }
     }
-    pub fn cols_mut(&mut self) -> ColumnsMut<T> {
+    pub fn cols_mut(&mut self) -> ColumnsMut<'_, T> {
         let end = self.raw_mut().row_size();
         ColumnsMut {
             grid: self.raw_mut(),
@@ -123,10 +190,261 @@ impl<T> Grid<T> {
             Coord::new(self.num_rows(), self.num_cols()),
         )
     }
+    pub const fn bounds(&self) -> AABB {
+        AABB::from_range(Coord::ORIGIN, Coord::new(self.num_rows(), self.num_cols()))
+    }
+
+    /// Draws one char per cell, with rows separated by newlines
+    pub fn render(&self, mut f: impl FnMut(Coord, &T) -> char) -> String {
+        render::render(self.bounds(), |c| f(c, &self[c]), None)
+    }
+    pub fn render_overlay(
+        &self,
+        mut f: impl FnMut(Coord, &T) -> char,
+        overlay: &Overlay,
+    ) -> String {
+        render::render(self.bounds(), |c| f(c, &self[c]), Some(overlay))
+    }
 
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
         self.data.iter_mut()
     }
+    /// The cells in row-major order
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+    // For rearranging cells in place without going through coords
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    /// In bounds orthogonal neighbours, clockwise from north
+    pub fn neighbors4(&self, coord: Coord) -> impl Iterator<Item = (Coord, &T)> {
+        self.neighbors4_dir(coord).map(|(_, c, v)| (c, v))
+    }
+    /// In bounds neighbours including diagonals, clockwise from north
+    pub fn neighbors8(&self, coord: Coord) -> impl Iterator<Item = (Coord, &T)> {
+        self.neighbors8_dir(coord).map(|(_, c, v)| (c, v))
+    }
+    /// Like `neighbors4` with the step taken to reach each neighbour first
+    pub fn neighbors4_dir(&self, coord: Coord) -> impl Iterator<Item = (Coord, Coord, &T)> {
+        self.neighbors_in(coord, &Coord::DIRS4)
+    }
+    pub fn neighbors8_dir(&self, coord: Coord) -> impl Iterator<Item = (Coord, Coord, &T)> {
+        self.neighbors_in(coord, &Coord::DIRS8)
+    }
+    /// Neighbours whose cell passes `f`, e.g. the open tiles of a maze
+    pub fn neighbors4_where<'a>(
+        &'a self,
+        coord: Coord,
+        mut f: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = (Coord, &'a T)> {
+        self.neighbors4(coord).filter(move |(_, v)| f(v))
+    }
+    pub fn neighbors8_where<'a>(
+        &'a self,
+        coord: Coord,
+        mut f: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = (Coord, &'a T)> {
+        self.neighbors8(coord).filter(move |(_, v)| f(v))
+    }
+    pub fn neighbors4_mut(&mut self, coord: Coord) -> impl Iterator<Item = (Coord, &mut T)> {
+        self.neighbors_in_mut(coord, &Coord::DIRS4)
+    }
+    pub fn neighbors8_mut(&mut self, coord: Coord) -> impl Iterator<Item = (Coord, &mut T)> {
+        self.neighbors_in_mut(coord, &Coord::DIRS8)
+    }
+
+    fn neighbors_in(
+        &self,
+        coord: Coord,
+        dirs: &'static [Coord],
+    ) -> impl Iterator<Item = (Coord, Coord, &T)> {
+        dirs.iter()
+            .filter_map(move |&d| Some((d, coord + d, self.get(coord + d)?)))
+    }
+    fn neighbors_in_mut(
+        &mut self,
+        coord: Coord,
+        dirs: &'static [Coord],
+    ) -> impl Iterator<Item = (Coord, &mut T)> {
+        let mut grid = self.raw_mut();
+        dirs.iter().filter_map(move |&d| {
+            // SAFETY: Every direction is different so no cell is handed out twice
+            let v = unsafe { grid.get_mut_unbound(coord + d) }?;
+            Some((coord + d, v))
+        })
+    }
+}
+
+// Resizing
+impl<T> Grid<T> {
+    /// Inserts `row` so that it ends up at `index`, a grid without rows takes its width from `row`
+    #[track_caller]
+    pub fn insert_row(&mut self, index: i64, row: Vec<T>) {
+        assert!(
+            (0..=self.num_rows()).contains(&index),
+            "Row {index} out of bounds"
+        );
+        let cols = if self.rows == 0 { row.len() } else { self.cols };
+        assert_eq!(row.len(), cols, "Row has the wrong length");
+
+        self.cols = cols;
+        let at = index as usize * self.cols;
+        self.data.splice(at..at, row);
+        self.rows += 1;
+    }
+    /// Inserts `col` so that it ends up at `index`, a grid without columns takes its height from
+    /// `col`
+    #[track_caller]
+    pub fn insert_col(&mut self, index: i64, col: Vec<T>) {
+        assert!(
+            (0..=self.num_cols()).contains(&index),
+            "Column {index} out of bounds"
+        );
+        let rows = if self.cols == 0 { col.len() } else { self.rows };
+        assert_eq!(col.len(), rows, "Column has the wrong length");
+
+        // Nothing below can panic, so the grid is never left half changed
+        self.rows = rows;
+        let mut old = std::mem::take(&mut self.data).into_iter();
+        let mut col = col.into_iter();
+        let cols = self.cols + 1;
+        self.data = (0..self.rows * cols)
+            .map(|i| {
+                if i % cols == index as usize {
+                    col.next().unwrap()
+                } else {
+                    old.next().unwrap()
+                }
+            })
+            .collect();
+        self.cols = cols;
+    }
+    #[track_caller]
+    pub fn remove_row(&mut self, index: i64) -> Vec<T> {
+        assert!(
+            (0..self.num_rows()).contains(&index),
+            "Row {index} out of bounds"
+        );
+        let at = index as usize * self.cols;
+        let row = self.data.drain(at..at + self.cols).collect();
+        self.rows -= 1;
+        if self.rows == 0 {
+            self.cols = 0;
+        }
+        row
+    }
+    #[track_caller]
+    pub fn remove_col(&mut self, index: i64) -> Vec<T> {
+        assert!(
+            (0..self.num_cols()).contains(&index),
+            "Column {index} out of bounds"
+        );
+        let mut col = Vec::with_capacity(self.rows);
+        let mut kept = Vec::with_capacity(self.data.len() - self.rows);
+        for (i, v) in std::mem::take(&mut self.data).into_iter().enumerate() {
+            if i % self.cols == index as usize {
+                col.push(v);
+            } else {
+                kept.push(v);
+            }
+        }
+        self.data = kept;
+        self.cols -= 1;
+        if self.cols == 0 {
+            self.rows = 0;
+        }
+        col
+    }
+
+    /// Surrounds the grid with `border` cells of `value` on every side
+    pub fn pad(&mut self, border: usize, value: T)
+    where
+        T: Clone,
+    {
+        let b = border as i64;
+        let (old_rows, old_cols) = self.size();
+        let (rows, cols) = (self.rows + 2 * border, self.cols + 2 * border);
+        let mut old = std::mem::take(self).data.into_iter();
+        *self = Grid::from_fn(rows, cols, |c| {
+            if (b..b + old_rows).contains(&c.row) && (b..b + old_cols).contains(&c.col) {
+                old.next().unwrap()
+            } else {
+                value.clone()
+            }
+        });
+    }
+    /// Keeps only the cells inside `area`, any part of `area` outside the grid is ignored
+    pub fn crop(&mut self, area: AABB) {
+        let start = Coord::new(area.start().row.max(0), area.start().col.max(0));
+        let end = Coord::new(
+            area.end().row.min(self.num_rows()),
+            area.end().col.min(self.num_cols()),
+        );
+        if start.row >= end.row || start.col >= end.col {
+            *self = Grid::default();
+            return;
+        }
+
+        let coords = self.coords();
+        let data = std::mem::take(&mut self.data)
+            .into_iter()
+            .zip(coords)
+            .filter(|(_, c)| {
+                (start.row..end.row).contains(&c.row) && (start.col..end.col).contains(&c.col)
+            })
+            .map(|(v, _)| v)
+            .collect();
+        let size = end - start;
+        *self = Grid::new(size.row as usize, size.col as usize, data);
+    }
+
+    /// Repeats every row and every column that `f` picks `copies` more times, e.g. to double the
+    /// empty rows and columns of a map. Rows and columns are all picked before any are copied
+    pub fn expand_where(&mut self, copies: usize, mut f: impl FnMut(&[&T]) -> bool)
+    where
+        T: Clone,
+    {
+        let rows: Vec<_> = (0..self.num_rows())
+            .filter(|&r| f(&self.row(r).iter().collect::<Vec<_>>()))
+            .collect();
+        let cols: Vec<_> = (0..self.num_cols())
+            .filter(|&c| f(&self.col(c).iter().collect::<Vec<_>>()))
+            .collect();
+
+        // Back to front so earlier indices stay valid
+        for &r in rows.iter().rev() {
+            let row: Vec<T> = self.row(r).iter().cloned().collect();
+            for _ in 0..copies {
+                self.insert_row(r, row.clone());
+            }
+        }
+        for &c in cols.iter().rev() {
+            let col: Vec<T> = self.col(c).iter().cloned().collect();
+            for _ in 0..copies {
+                self.insert_col(c, col.clone());
+            }
+        }
+    }
+}
+
+impl Display for Grid<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(|_, &c| c))
+    }
+}
+impl Display for Grid<u8> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(|_, &b| b as char))
+    }
+}
+
+impl Grid<char> {
+    #[track_caller]
+    pub fn parse_chars(input: &str) -> Self {
+        Self::parse_with(input, |c| c)
+    }
 }
 
 impl<T> IndexMut<Coord> for Grid<T> {
@@ -172,7 +490,8 @@ impl Iterator for Coords {
     type Item = Coord;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current.row == self.end.row {
+        // Also covers empty boxes like `AABB::new()`, whose end is before its start
+        if self.current.row >= self.end.row || self.start.col >= self.end.col {
             None
         } else {
             let mut next = self.current + Coord::new(0, 1);
@@ -190,6 +509,11 @@ impl Iterator for Coords {
 pub struct GridBuilder<T> {
     rows: Vec<Vec<T>>,
 }
+impl<T> Default for GridBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 impl<T> GridBuilder<T> {
     pub const fn new() -> Self {
         Self { rows: Vec::new() }
@@ -224,7 +548,7 @@ impl<T> GridBuilder<T> {
     }
 }
 
-fn coord_to_index(c: Coord, rows: i64, cols: i64) -> Option<usize> {
+pub(crate) fn coord_to_index(c: Coord, rows: i64, cols: i64) -> Option<usize> {
     if c.row < 0 || c.col < 0 {
         return None;
     }
@@ -306,7 +630,14 @@ impl<'grid, T> RawGridMut<'grid, T> {
     }
     /// # Safety
     /// The returned grid must not issue mutable refs issued by any grid made with this lifetime
-    unsafe fn unchecked_copy(&mut self) -> Self {
+    ///
+    /// Taking `&self` rather than `&mut self` lets the parallel iterators copy from a grid shared
+    /// between threads. That doesn't weaken anything: the copy only duplicates the pointer and
+    /// the size, nothing is read through `self`, and the uniqueness of the `&mut T` handed out
+    /// was always down to the caller's promise above, never to the borrow of `self`. Callers
+    /// that send copies to other threads must also require `T: Send`, as each cell is then
+    /// mutated from whichever thread got its row or column
+    unsafe fn unchecked_copy(&self) -> Self {
         Self {
             marker: self.marker,
             ptr: self.ptr,
@@ -321,7 +652,7 @@ impl<'grid, T> RawGridMut<'grid, T> {
         self.rows
     }
 
-    fn as_ref(&self) -> RawGridRef<T> {
+    fn as_ref(&self) -> RawGridRef<'_, T> {
         RawGridRef {
             data: PhantomData,
             ptr: self.ptr as *const _,
@@ -407,7 +738,7 @@ pub struct RowsMut<'grid, T> {
     end: i64,
 }
 impl<'grid, T> RowsMut<'grid, T> {
-    pub fn iter(&self) -> RowsIter<T> {
+    pub fn iter(&self) -> RowsIter<'_, T> {
         RowsIter {
             grid: self.grid.as_ref(),
             start: self.start,
@@ -487,7 +818,7 @@ impl<'grid, T> Iterator for RowsMutIntoIter<'grid, T> {
 }
 impl<'grid, T> DoubleEndedIterator for RowsMutIntoIter<'grid, T> {
     fn next_back(&mut self) -> Option<Self::Item> {
-        if self.start > self.end {
+        if self.start == self.end {
             None
         } else {
             self.end -= 1;
@@ -564,7 +895,7 @@ pub struct ColumnsMut<'grid, T> {
     end: i64,
 }
 impl<'grid, T> ColumnsMut<'grid, T> {
-    pub fn iter(&self) -> ColumnsIter<T> {
+    pub fn iter(&self) -> ColumnsIter<'_, T> {
         ColumnsIter {
             grid: self.grid.as_ref(),
             start: self.start,
@@ -680,7 +1011,7 @@ impl<'grid, T> Row<'grid, T> {
             grid: self.grid,
             row: self.row,
             start: self.start,
-            end: self.grid.row_size(),
+            end: self.end,
         }
     }
     pub const fn coord_of(&self, index: i64) -> Coord {
@@ -842,7 +1173,7 @@ impl<'grid, T> RowMut<'grid, T> {
     pub fn get_mut(&mut self, index: i64) -> Option<&mut T> {
         self.grid.get_mut(self.coord_of(index))
     }
-    pub fn as_ref(&self) -> Row<T> {
+    pub fn as_ref(&self) -> Row<'_, T> {
         Row {
             grid: self.grid.as_ref(),
             start: self.start,
@@ -850,7 +1181,7 @@ impl<'grid, T> RowMut<'grid, T> {
             row: self.row,
         }
     }
-    pub fn iter(&self) -> RowIter<T> {
+    pub fn iter(&self) -> RowIter<'_, T> {
         self.as_ref().iter()
     }
     pub fn iter_mut<'row>(&'row mut self) -> RowMutIter<'grid, 'row, T> {
@@ -862,7 +1193,7 @@ impl<'grid, T> RowMut<'grid, T> {
         }
     }
     #[track_caller]
-    pub fn slice<I: RangeBounds<i64>>(&self, range: I) -> Row<T> {
+    pub fn slice<I: RangeBounds<i64>>(&self, range: I) -> Row<'_, T> {
         self.as_ref().slice(range)
     }
     #[track_caller]
@@ -1208,7 +1539,7 @@ impl<'grid, T> ColumnMut<'grid, T> {
     pub fn get_mut(&mut self, index: i64) -> Option<&mut T> {
         self.grid.get_mut(self.coord_of(index))
     }
-    pub fn as_ref(&self) -> Column<T> {
+    pub fn as_ref(&self) -> Column<'_, T> {
         Column {
             grid: self.grid.as_ref(),
             start: self.start,
@@ -1217,7 +1548,7 @@ impl<'grid, T> ColumnMut<'grid, T> {
         }
     }
     #[track_caller]
-    pub fn slice<I: RangeBounds<i64>>(&self, range: I) -> Column<T> {
+    pub fn slice<I: RangeBounds<i64>>(&self, range: I) -> Column<'_, T> {
         self.as_ref().slice(range)
     }
     #[track_caller]
@@ -1241,7 +1572,7 @@ impl<'grid, T> ColumnMut<'grid, T> {
             col: self.col,
         }
     }
-    pub fn iter(&self) -> ColumnIter<T> {
+    pub fn iter(&self) -> ColumnIter<'_, T> {
         self.as_ref().iter()
     }
     pub fn iter_mut<'col>(&'col mut self) -> ColumnMutIter<'grid, 'col, T> {
@@ -1453,6 +1784,25 @@ impl Coord {
     pub const E: Coord = Coord::new(0, 1);
     pub const W: Coord = Coord::new(0, -1);
 
+    pub const NE: Coord = Coord::new(-1, 1);
+    pub const NW: Coord = Coord::new(-1, -1);
+    pub const SE: Coord = Coord::new(1, 1);
+    pub const SW: Coord = Coord::new(1, -1);
+
+    /// Clockwise from north
+    pub const DIRS4: [Coord; 4] = [Coord::N, Coord::E, Coord::S, Coord::W];
+    /// Clockwise from north
+    pub const DIRS8: [Coord; 8] = [
+        Coord::N,
+        Coord::NE,
+        Coord::E,
+        Coord::SE,
+        Coord::S,
+        Coord::SW,
+        Coord::W,
+        Coord::NW,
+    ];
+
     pub const ORIGIN: Coord = Coord::new(0, 0);
 
     pub const MAX: Coord = Coord::new(i64::MAX, i64::MAX);
@@ -1471,6 +1821,40 @@ impl Coord {
     pub fn dist_taxicab(self, other: Coord) -> i64 {
         self.dist_manhatten(other)
     }
+    /// The number of king moves between the two, diagonals counting as one step
+    pub fn dist_chebyshev(self, other: Coord) -> i64 {
+        let d = (self - other).abs();
+        d.row.max(d.col)
+    }
+    /// The squared straight line distance, which keeps to integers and orders the same way
+    pub fn dist_euclidean_sq(self, other: Coord) -> i64 {
+        let d = self - other;
+        d.row * d.row + d.col * d.col
+    }
+
+    pub fn abs(self) -> Self {
+        Coord::new(self.row.abs(), self.col.abs())
+    }
+    /// Each component as -1, 0 or 1, turning an offset along a line or diagonal into a single step
+    pub fn signum(self) -> Self {
+        Coord::new(self.row.signum(), self.col.signum())
+    }
+    /// `i64::div_euclid` on each component, pairs with `rem_euclid`
+    pub fn div_euclid(self, rhs: i64) -> Self {
+        Coord::new(self.row.div_euclid(rhs), self.col.div_euclid(rhs))
+    }
+    /// `i64::rem_euclid` on each component. Never negative, so wraps a coord into a square of
+    /// that size
+    pub fn rem_euclid(self, rhs: i64) -> Self {
+        Coord::new(self.row.rem_euclid(rhs), self.col.rem_euclid(rhs))
+    }
+
+    pub fn neighbors4(self) -> [Coord; 4] {
+        Coord::DIRS4.map(|d| self + d)
+    }
+    pub fn neighbors8(self) -> [Coord; 8] {
+        Coord::DIRS8.map(|d| self + d)
+    }
 
     pub fn rotate_cw_90(self) -> Self {
         Coord::new(self.col, -self.row)
@@ -1479,6 +1863,32 @@ impl Coord {
     pub fn rotate_ccw_90(self) -> Self {
         Coord::new(-self.col, self.row)
     }
+    pub fn rotate_180(self) -> Self {
+        -self
+    }
+
+    pub fn rotate_cw_90_about(self, pivot: Coord) -> Self {
+        (self - pivot).rotate_cw_90() + pivot
+    }
+    pub fn rotate_ccw_90_about(self, pivot: Coord) -> Self {
+        (self - pivot).rotate_ccw_90() + pivot
+    }
+    pub fn rotate_180_about(self, pivot: Coord) -> Self {
+        (self - pivot).rotate_180() + pivot
+    }
+}
+
+impl From<(i64, i64)> for Coord {
+    fn from((row, col): (i64, i64)) -> Self {
+        Self::new(row, col)
+    }
+}
+
+/// `(row, col)`
+impl Display for Coord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.row, self.col)
+    }
 }
 
 impl PartialOrd for Coord {
@@ -1491,7 +1901,7 @@ impl PartialOrd for Coord {
             (Ordering::Equal, Ordering::Less) => Some(Ordering::Less),
             (Ordering::Equal, Ordering::Greater) => Some(Ordering::Greater),
             (Ordering::Less, Ordering::Equal) => Some(Ordering::Less),
-            (Ordering::Greater, Ordering::Equal) => Some(Ordering::Equal),
+            (Ordering::Greater, Ordering::Equal) => Some(Ordering::Greater),
 
             _ => None,
         }
@@ -1552,6 +1962,53 @@ impl MulAssign<u64> for Coord {
         *self *= rhs as i64
     }
 }
+impl Neg for Coord {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Coord::new(-self.row, -self.col)
+    }
+}
+/// Truncates towards zero like `i64`, see `div_euclid` to round down instead
+impl Div<i64> for Coord {
+    type Output = Self;
+
+    fn div(mut self, rhs: i64) -> Self::Output {
+        self /= rhs;
+        self
+    }
+}
+impl DivAssign<i64> for Coord {
+    fn div_assign(&mut self, rhs: i64) {
+        self.row /= rhs;
+        self.col /= rhs;
+    }
+}
+/// Takes the sign of the coord like `i64`, see `rem_euclid` for one that is never negative
+impl Rem<i64> for Coord {
+    type Output = Self;
+
+    fn rem(mut self, rhs: i64) -> Self::Output {
+        self %= rhs;
+        self
+    }
+}
+impl RemAssign<i64> for Coord {
+    fn rem_assign(&mut self, rhs: i64) {
+        self.row %= rhs;
+        self.col %= rhs;
+    }
+}
+impl Sum for Coord {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Coord::ORIGIN, Add::add)
+    }
+}
+impl<'a> Sum<&'a Coord> for Coord {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
 
@@ -1560,6 +2017,12 @@ pub struct AABB {
     end: Coord,
 }
 
+impl Default for AABB {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AABB {
     pub const fn new() -> Self {
         AABB {
@@ -1568,6 +2031,11 @@ impl AABB {
         }
     }
 
+    /// Covers `start` up to but not including `end`
+    pub const fn from_range(start: Coord, end: Coord) -> Self {
+        AABB { start, end }
+    }
+
     pub fn from_points(points: impl IntoIterator<Item = Coord>) -> Self {
         let mut aabb = Self::new();
         for p in points.into_iter() {
@@ -1589,8 +2057,16 @@ impl AABB {
         }
     }
 
+    pub const fn start(&self) -> Coord {
+        self.start
+    }
+    pub const fn end(&self) -> Coord {
+        self.end
+    }
+
     pub fn contains(&self, c: Coord) -> bool {
-        self.start <= c && c < self.end
+        (self.start.row..self.end.row).contains(&c.row)
+            && (self.start.col..self.end.col).contains(&c.col)
     }
 
     pub fn coords(&self) -> Coords {
@@ -1613,7 +2089,7 @@ impl Iterator for RowCoords {
     type Item = Coords;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.start.row == self.end.row {
+        if self.start.row >= self.end.row {
             None
         } else {
             let start = self.start;
@@ -1625,7 +2101,7 @@ impl Iterator for RowCoords {
 }
 impl DoubleEndedIterator for RowCoords {
     fn next_back(&mut self) -> Option<Self::Item> {
-        if self.start.row == self.end.row {
+        if self.start.row >= self.end.row {
             None
         } else {
             self.end.row -= 1;
@@ -1712,11 +2188,12 @@ macro_rules! impl_traits {
     };
 }
 
-impl_traits!(Column ColumnMut Row RowMut);
+impl_traits!(Column ColumnMut Row RowMut TransformedRow TransformedColumn);
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::render::Color;
 
     #[test]
     fn rows() {
@@ -1783,6 +2260,12 @@ mod tests {
             assert_eq!(5, c.iter_mut().rev().count());
             assert_eq!(5, c.into_iter().rev().count());
         }
+
+        // Not square
+        let mut grid = Grid::filled(2, 3, 0);
+        assert_eq!(2, grid.rows_mut().into_iter().count());
+        assert_eq!(2, grid.rows_mut().into_iter().rev().count());
+        assert_eq!(3, grid.cols_mut().into_iter().count());
     }
 
     #[test]
@@ -1924,5 +2407,250 @@ mod tests {
     fn coord_rotate() {
         assert_eq!(Coord::N.rotate_cw_90(), Coord::E);
         assert_eq!(Coord::N.rotate_ccw_90(), Coord::W);
+
+        let pivot = Coord::new(2, 2);
+        let top = Coord::new(0, 2);
+        assert_eq!(top.rotate_cw_90_about(pivot), Coord::new(2, 4));
+        assert_eq!(top.rotate_ccw_90_about(pivot), Coord::new(2, 0));
+        assert_eq!(Coord::new(0, 3).rotate_180_about(pivot), Coord::new(4, 1));
+        assert_eq!(pivot.rotate_cw_90_about(pivot), pivot);
+    }
+
+    #[test]
+    fn coord_ops() {
+        let a = Coord::new(3, -7);
+        assert_eq!(-a, Coord::new(-3, 7));
+        assert_eq!(a.abs(), Coord::new(3, 7));
+        assert_eq!(a.signum(), Coord::new(1, -1));
+        assert_eq!(a / 2, Coord::new(1, -3));
+        assert_eq!(a / -2, Coord::new(-1, 3));
+        assert_eq!(a % 5, Coord::new(3, -2));
+        assert_eq!((a / 5) * 5i64 + a % 5, a);
+        assert_eq!(a.div_euclid(2), Coord::new(1, -4));
+        assert_eq!(a.div_euclid(-2), Coord::new(-1, 4));
+        assert_eq!(a.rem_euclid(5), Coord::new(3, 3));
+        assert_eq!(a.rem_euclid(-5), Coord::new(3, 3));
+        assert_eq!(a.div_euclid(-5) * -5i64 + a.rem_euclid(-5), a);
+
+        assert_eq!(a.dist_manhatten(Coord::ORIGIN), 10);
+        assert_eq!(a.dist_chebyshev(Coord::new(1, 1)), 8);
+        assert_eq!(a.dist_euclidean_sq(Coord::new(0, -3)), 25);
+
+        assert_eq!(Coord::from((3, -7)), a);
+        assert_eq!(a.to_string(), "(3, -7)");
+        assert_eq!(Coord::DIRS8.iter().sum::<Coord>(), Coord::ORIGIN);
+        assert_eq!([Coord::N; 3].into_iter().sum::<Coord>(), Coord::new(-3, 0));
+    }
+
+    #[test]
+    fn coord_ord() {
+        let a = Coord::new(1, 1);
+        assert!(Coord::new(2, 1) > a);
+        assert!(Coord::new(1, 0) < a);
+        assert!(Coord::new(2, 2) >= a);
+        assert_eq!(Coord::new(0, 2).partial_cmp(&a), None);
+
+        let aabb = AABB::from_range(Coord::ORIGIN, Coord::new(2, 3));
+        assert!(aabb.contains(Coord::new(1, 2)));
+        assert!(!aabb.contains(Coord::new(2, 0)));
+        assert!(!aabb.contains(Coord::new(0, 3)));
+        assert!(!aabb.contains(Coord::new(-1, 1)));
+        assert!(!AABB::new().contains(Coord::ORIGIN));
+    }
+
+    #[test]
+    fn new() {
+        let grid = Grid::new(2, 3, vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(grid.size(), (2, 3));
+        assert_eq!(grid[Coord::new(1, 0)], 4);
+
+        assert_eq!(Grid::filled(2, 2, 'x'), Grid::from([['x'; 2]; 2]));
+        assert_eq!(
+            Grid::from_fn(2, 3, |c| c.row * 10 + c.col),
+            Grid::from([[0, 1, 2], [10, 11, 12]])
+        );
+    }
+
+    #[test]
+    fn parse() {
+        let input = "
+            #.#
+            ..#
+
+        ";
+        let grid = Grid::parse_chars(input);
+        assert_eq!(grid, Grid::from([['#', '.', '#'], ['.', '.', '#']]));
+        assert_eq!(Grid::parse_chars("#.#\n..#\n"), grid);
+        assert_eq!(Grid::parse_chars(input.trim()), grid);
+
+        let grid = Grid::parse_with(input, |c| c == '#');
+        assert_eq!(grid.row(1), [false, false, true]);
+
+        // Only the shared indentation is removed
+        let grid = Grid::parse_chars("\n   .#\n  #..\n");
+        assert_eq!(grid.row(0), [' ', '.', '#']);
+        let grid = Grid::parse_chars("\n\t\u{3000} #\n\t\u{3000}# \n");
+        assert_eq!(grid, Grid::from([[' ', '#'], ['#', ' ']]));
+
+        let grid: Grid<i64> = Grid::parse_digits("  123\n  456\n");
+        assert_eq!(grid.col(2), [3, 6]);
+
+        // Padding that some line goes without is part of the map
+        let grid = Grid::parse_chars("  #\n # \n#  ");
+        assert_eq!(grid.col(0), [' ', ' ', '#']);
+        assert_eq!(grid.row(1), [' ', '#', ' ']);
+
+        assert_eq!(Grid::<char>::parse_chars("\n  \n"), Grid::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "Uneven row lengths")]
+    fn parse_uneven() {
+        Grid::parse_chars("###\n##");
+    }
+
+    #[test]
+    fn render() {
+        let grid = Grid::parse_chars("#..\n.#.");
+        assert_eq!(grid.to_string(), "#..\n.#.");
+
+        let bytes = Grid::parse_with("ab\ncd", |c| c as u8);
+        assert_eq!(bytes.to_string(), "ab\ncd");
+
+        let out = grid.render(|c, &v| {
+            if v == '#' {
+                'X'
+            } else {
+                char::from(b'0' + c.col as u8)
+            }
+        });
+        assert_eq!(out, "X12\n0X2");
+
+        let overlay = Overlay::new()
+            .replace([Coord::new(0, 1), Coord::new(1, 2)], 'o')
+            .color([Coord::new(1, 2)], Color::Red);
+        let out = grid.render_overlay(|_, &c| c, &overlay);
+        assert_eq!(out, "#o.\n.#\x1b[31m.\x1b[0m");
+
+        assert_eq!(Grid::<char>::default().to_string(), "");
+    }
+
+    #[test]
+    fn neighbors() {
+        assert_eq!(
+            Coord::new(1, 1).neighbors4(),
+            [(0, 1), (1, 2), (2, 1), (1, 0)].map(|(r, c)| Coord::new(r, c))
+        );
+        assert_eq!(Coord::ORIGIN.neighbors8().len(), 8);
+
+        let mut grid = Grid::parse_digits("123\n456\n789");
+        let values =
+            |it: &mut dyn Iterator<Item = (Coord, &u8)>| it.map(|(_, &v)| v).collect::<Vec<_>>();
+
+        assert_eq!(values(&mut grid.neighbors4(Coord::new(1, 1))), [2, 6, 8, 4]);
+        assert_eq!(values(&mut grid.neighbors4(Coord::ORIGIN)), [2, 4]);
+        assert_eq!(values(&mut grid.neighbors8(Coord::ORIGIN)), [2, 5, 4]);
+        assert_eq!(
+            values(&mut grid.neighbors8(Coord::new(1, 1))),
+            [2, 3, 6, 9, 8, 7, 4, 1]
+        );
+        assert_eq!(
+            values(&mut grid.neighbors8_where(Coord::new(1, 1), |v| v % 2 == 0)),
+            [2, 6, 8, 4]
+        );
+        assert_eq!(
+            values(&mut grid.neighbors4_where(Coord::new(2, 2), |&v| v > 6)),
+            [8]
+        );
+
+        let dirs: Vec<_> = grid
+            .neighbors4_dir(Coord::new(0, 2))
+            .map(|(d, _, _)| d)
+            .collect();
+        assert_eq!(dirs, [Coord::S, Coord::W]);
+
+        for (_, v) in grid.neighbors4_mut(Coord::new(1, 1)) {
+            *v = 0;
+        }
+        assert_eq!(grid, Grid::from([[1, 0, 3], [0, 5, 0], [7, 0, 9]]));
+        assert_eq!(grid.neighbors8_mut(Coord::new(2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut grid = Grid::from([[1, 2], [3, 4]]);
+        grid.insert_row(1, vec![5, 6]);
+        assert_eq!(grid, Grid::from([[1, 2], [5, 6], [3, 4]]));
+        grid.insert_col(2, vec![7, 8, 9]);
+        assert_eq!(grid, Grid::from([[1, 2, 7], [5, 6, 8], [3, 4, 9]]));
+        grid.insert_col(0, vec![0, 0, 0]);
+        assert_eq!(grid.row(2), [0, 3, 4, 9]);
+
+        assert_eq!(grid.remove_col(0), [0, 0, 0]);
+        assert_eq!(grid.remove_row(0), [1, 2, 7]);
+        assert_eq!(grid, Grid::from([[5, 6, 8], [3, 4, 9]]));
+        assert_eq!(grid.remove_col(1), [6, 4]);
+        assert_eq!(grid, Grid::from([[5, 8], [3, 9]]));
+
+        let mut grid = Grid::default();
+        grid.insert_col(0, vec!['a', 'b']);
+        assert_eq!(grid.size(), (2, 1));
+        grid.remove_row(0);
+        grid.remove_row(0);
+        assert_eq!(grid, Grid::default());
+    }
+
+    #[test]
+    fn insert_empty() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        // 3x0 has rows, so a row has to match its width of 0 and a column sets the height
+        let mut grid = Grid::filled(3, 0, 0);
+        assert!(catch_unwind(AssertUnwindSafe(|| grid.insert_row(3, vec![1, 2]))).is_err());
+        assert_eq!(grid.size(), (3, 0));
+        grid.insert_row(3, Vec::new());
+        assert_eq!(grid.size(), (4, 0));
+        grid.insert_col(0, vec![1, 2, 3, 4]);
+        assert_eq!(grid, Grid::from([[1], [2], [3], [4]]));
+
+        // And the other way round for 0x2
+        let mut grid = Grid::filled(0, 2, 0);
+        assert!(catch_unwind(AssertUnwindSafe(|| grid.insert_col(0, vec![1, 2]))).is_err());
+        assert_eq!(grid.size(), (0, 2));
+        grid.insert_col(2, Vec::new());
+        assert_eq!(grid.size(), (0, 3));
+        grid.insert_row(0, vec![1, 2, 3, 4]);
+        assert_eq!(grid, Grid::from([[1, 2, 3, 4]]));
+    }
+
+    #[test]
+    fn pad_crop() {
+        let mut grid = Grid::parse_chars("ab\ncd");
+        grid.pad(1, '.');
+        assert_eq!(grid.to_string(), "....\n.ab.\n.cd.\n....");
+
+        let mut cropped = grid.clone();
+        cropped.crop(AABB::from_range(Coord::new(1, 1), Coord::new(3, 2)));
+        assert_eq!(cropped.to_string(), "a\nc");
+
+        // Clipped to the grid
+        grid.crop(AABB::from_range(Coord::new(-5, 2), Coord::new(2, 10)));
+        assert_eq!(grid.to_string(), "..\nb.");
+
+        grid.crop(AABB::from_range(Coord::new(5, 5), Coord::new(6, 6)));
+        assert_eq!(grid, Grid::default());
+    }
+
+    #[test]
+    fn expand() {
+        let mut grid = Grid::parse_chars(
+            "
+            #..
+            ...
+            ..#
+            ",
+        );
+        grid.expand_where(1, |line| line.iter().all(|&&c| c == '.'));
+        assert_eq!(grid.to_string(), "#...\n....\n....\n...#");
     }
 }
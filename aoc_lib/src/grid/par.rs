@@ -0,0 +1,118 @@
+use rayon::prelude::*;
+
+use super::{Column, ColumnMut, Coord, Grid, RawGridMut, Row, RowMut};
+
+/// Parallel versions of the row and column views, each item can be sent to its own thread
+impl<T> Grid<T> {
+    pub fn par_rows(&self) -> impl IndexedParallelIterator<Item = Row<'_, T>>
+    where
+        T: Sync,
+    {
+        let grid = self.raw_ref();
+        (0..self.rows)
+            .into_par_iter()
+            .map(move |r| Row::new(grid, r as i64))
+    }
+    pub fn par_cols(&self) -> impl IndexedParallelIterator<Item = Column<'_, T>>
+    where
+        T: Sync,
+    {
+        let grid = self.raw_ref();
+        (0..self.cols)
+            .into_par_iter()
+            .map(move |c| Column::new(grid, c as i64))
+    }
+    pub fn par_rows_mut(&mut self) -> impl IndexedParallelIterator<Item = RowMut<'_, T>>
+    where
+        T: Send,
+    {
+        let rows = self.rows;
+        let grid = Shared(self.raw_mut());
+        // SAFETY: Every row is handed out once and rows don't overlap
+        (0..rows)
+            .into_par_iter()
+            .map(move |r| RowMut::new(unsafe { grid.copy() }, r as i64))
+    }
+    pub fn par_cols_mut(&mut self) -> impl IndexedParallelIterator<Item = ColumnMut<'_, T>>
+    where
+        T: Send,
+    {
+        let cols = self.cols;
+        let grid = Shared(self.raw_mut());
+        // SAFETY: Every column is handed out once and columns don't overlap
+        (0..cols)
+            .into_par_iter()
+            .map(move |c| ColumnMut::new(unsafe { grid.copy() }, c as i64))
+    }
+    /// Every coord in row-major order
+    pub fn par_coords(&self) -> impl IndexedParallelIterator<Item = Coord> {
+        let cols = self.cols;
+        (0..self.rows * cols)
+            .into_par_iter()
+            .map(move |i| Coord::new((i / cols) as i64, (i % cols) as i64))
+    }
+}
+
+// The grid as seen by rayon's threads, which only ever make copies of it to hand out disjoint
+// rows or columns. Unlike `RawGridMut` this is `Sync` without `T: Sync`, since no cell is
+// reached through a shared reference
+struct Shared<'grid, T>(RawGridMut<'grid, T>);
+
+// SAFETY: `copy` is the only access through `&Shared` and it reads no cells
+unsafe impl<'grid, T: Send> Sync for Shared<'grid, T> {}
+
+impl<'grid, T> Shared<'grid, T> {
+    /// # Safety
+    /// Same as `RawGridMut::unchecked_copy`
+    unsafe fn copy(&self) -> RawGridMut<'grid, T> {
+        unsafe { self.0.unchecked_copy() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn parallel() {
+        let mut grid = Grid::from_fn(3, 5, |c| c.row * 10 + c.col);
+
+        let sums: Vec<i64> = grid.par_rows().map(|r| r.iter().sum()).collect();
+        assert_eq!(sums, [10, 60, 110]);
+        let sums: Vec<i64> = grid.par_cols().map(|c| c.iter().sum()).collect();
+        assert_eq!(sums, [30, 33, 36, 39, 42]);
+
+        grid.par_rows_mut().for_each(|mut r| {
+            for v in r.iter_mut() {
+                *v += 1;
+            }
+        });
+        grid.par_cols_mut().for_each(|mut c| {
+            for v in c.iter_mut().rev().take(1) {
+                *v = 0;
+            }
+        });
+        assert_eq!(
+            grid.row(0).iter().copied().collect::<Vec<_>>(),
+            [1, 2, 3, 4, 5]
+        );
+        assert!(grid.row(2).iter().all(|&v| v == 0));
+
+        let coords: Vec<_> = grid.par_coords().collect();
+        assert_eq!(coords, grid.coords().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn send_only() {
+        // `Cell` is `Send` but not `Sync`
+        let mut grid = Grid::from_fn(2, 3, |c| Cell::new(c.row * 10 + c.col));
+        grid.par_rows_mut()
+            .for_each(|r| r.iter().for_each(|v| v.set(v.get() + 1)));
+        grid.par_cols_mut()
+            .for_each(|mut c| c.iter_mut().for_each(|v| *v.get_mut() *= 2));
+        let values: Vec<_> = grid.row(1).iter().map(Cell::get).collect();
+        assert_eq!(values, [22, 24, 26]);
+    }
+}
@@ -0,0 +1,383 @@
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use crate::{
+    grid::{Coord, AABB},
+    render,
+};
+
+#[derive(Debug, PartialEq, Eq, Default, Hash, Copy, Clone)]
+pub struct Coord3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Coord3 {
+    pub const X: Coord3 = Coord3::new(1, 0, 0);
+    pub const Y: Coord3 = Coord3::new(0, 1, 0);
+    pub const Z: Coord3 = Coord3::new(0, 0, 1);
+
+    /// One step along each axis in both directions
+    pub const DIRS6: [Coord3; 6] = [
+        Coord3::new(1, 0, 0),
+        Coord3::new(-1, 0, 0),
+        Coord3::new(0, 1, 0),
+        Coord3::new(0, -1, 0),
+        Coord3::new(0, 0, 1),
+        Coord3::new(0, 0, -1),
+    ];
+    /// Every offset in the surrounding 3x3x3 cube, with x changing fastest
+    pub const DIRS26: [Coord3; 26] = {
+        let mut dirs = [Coord3::ORIGIN; 26];
+        let (mut i, mut n) = (0, 0);
+        while i < 27 {
+            // Skip the centre
+            if i != 13 {
+                dirs[n] = Coord3::new(i % 3 - 1, i / 3 % 3 - 1, i / 9 - 1);
+                n += 1;
+            }
+            i += 1;
+        }
+        dirs
+    };
+
+    pub const ORIGIN: Coord3 = Coord3::new(0, 0, 0);
+
+    pub const MAX: Coord3 = Coord3::new(i64::MAX, i64::MAX, i64::MAX);
+    pub const MIN: Coord3 = Coord3::new(i64::MIN, i64::MIN, i64::MIN);
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn dist_manhatten(self, other: Coord3) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    pub fn neighbors6(self) -> [Coord3; 6] {
+        Coord3::DIRS6.map(|d| self + d)
+    }
+    pub fn neighbors26(self) -> [Coord3; 26] {
+        Coord3::DIRS26.map(|d| self + d)
+    }
+}
+
+impl From<(i64, i64, i64)> for Coord3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl Add for Coord3 {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+impl Sub for Coord3 {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+impl AddAssign for Coord3 {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+impl SubAssign for Coord3 {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+impl Mul<i64> for Coord3 {
+    type Output = Self;
+
+    fn mul(mut self, rhs: i64) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+impl MulAssign<i64> for Coord3 {
+    fn mul_assign(&mut self, rhs: i64) {
+        self.x *= rhs;
+        self.y *= rhs;
+        self.z *= rhs;
+    }
+}
+impl Neg for Coord3 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self * -1
+    }
+}
+
+/// The 3D version of `AABB`
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct AABB3 {
+    start: Coord3,
+    end: Coord3,
+}
+
+impl Default for AABB3 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AABB3 {
+    pub const fn new() -> Self {
+        AABB3 {
+            start: Coord3::MAX,
+            end: Coord3::MIN,
+        }
+    }
+
+    /// Covers `start` up to but not including `end`
+    pub const fn from_range(start: Coord3, end: Coord3) -> Self {
+        AABB3 { start, end }
+    }
+
+    pub fn from_points(points: impl IntoIterator<Item = Coord3>) -> Self {
+        let mut aabb = Self::new();
+        for p in points {
+            aabb.add(p);
+        }
+        aabb
+    }
+
+    pub fn add(&mut self, c: Coord3) {
+        self.start.x = self.start.x.min(c.x);
+        self.start.y = self.start.y.min(c.y);
+        self.start.z = self.start.z.min(c.z);
+
+        self.end.x = self.end.x.max(c.x + 1);
+        self.end.y = self.end.y.max(c.y + 1);
+        self.end.z = self.end.z.max(c.z + 1);
+    }
+
+    pub const fn start(&self) -> Coord3 {
+        self.start
+    }
+    pub const fn end(&self) -> Coord3 {
+        self.end
+    }
+
+    pub fn contains(&self, c: Coord3) -> bool {
+        (self.start.x..self.end.x).contains(&c.x)
+            && (self.start.y..self.end.y).contains(&c.y)
+            && (self.start.z..self.end.z).contains(&c.z)
+    }
+
+    /// Every coord inside, with x changing fastest and z slowest
+    pub fn coords(&self) -> impl Iterator<Item = Coord3> {
+        let (start, end) = (self.start, self.end);
+        (start.z..end.z).flat_map(move |z| {
+            (start.y..end.y).flat_map(move |y| (start.x..end.x).map(move |x| Coord3::new(x, y, z)))
+        })
+    }
+}
+
+/// A dense 3D grid indexed from the origin, stored with x changing fastest
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Grid3<T> {
+    size: (usize, usize, usize),
+    data: Vec<T>,
+}
+
+impl<T> Default for Grid3<T> {
+    fn default() -> Self {
+        Self {
+            size: (0, 0, 0),
+            data: Vec::new(),
+        }
+    }
+}
+
+impl<T> Grid3<T> {
+    #[track_caller]
+    pub fn new(x: usize, y: usize, z: usize, data: Vec<T>) -> Self {
+        assert_eq!(x * y * z, data.len(), "Data doesn't match the size");
+        Self {
+            size: (x, y, z),
+            data,
+        }
+    }
+    pub fn filled(x: usize, y: usize, z: usize, v: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(x, y, z, vec![v; x * y * z])
+    }
+    pub fn from_fn(x: usize, y: usize, z: usize, f: impl FnMut(Coord3) -> T) -> Self {
+        let bounds = AABB3::from_range(Coord3::ORIGIN, Coord3::new(x as i64, y as i64, z as i64));
+        Self::new(x, y, z, bounds.coords().map(f).collect())
+    }
+
+    pub const fn size(&self) -> (i64, i64, i64) {
+        (self.size.0 as i64, self.size.1 as i64, self.size.2 as i64)
+    }
+    pub const fn bounds(&self) -> AABB3 {
+        let (x, y, z) = self.size();
+        AABB3::from_range(Coord3::ORIGIN, Coord3::new(x, y, z))
+    }
+    pub fn contains(&self, c: Coord3) -> bool {
+        self.bounds().contains(c)
+    }
+
+    fn index_of(&self, c: Coord3) -> Option<usize> {
+        if !self.contains(c) {
+            return None;
+        }
+        let (x, y, _) = self.size;
+        Some((c.z as usize * y + c.y as usize) * x + c.x as usize)
+    }
+    pub fn get(&self, c: Coord3) -> Option<&T> {
+        self.data.get(self.index_of(c)?)
+    }
+    pub fn get_mut(&mut self, c: Coord3) -> Option<&mut T> {
+        let index = self.index_of(c)?;
+        self.data.get_mut(index)
+    }
+
+    pub fn coords(&self) -> impl Iterator<Item = Coord3> {
+        self.bounds().coords()
+    }
+    /// Cells in storage order, the same order as `coords`
+    pub fn iter(&self) -> impl Iterator<Item = (Coord3, &T)> {
+        self.coords().zip(&self.data)
+    }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.data.iter_mut()
+    }
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    /// The in bounds face neighbours of `c`
+    pub fn neighbors6(&self, c: Coord3) -> impl Iterator<Item = (Coord3, &T)> {
+        c.neighbors6()
+            .into_iter()
+            .filter_map(|n| Some((n, self.get(n)?)))
+    }
+    /// The in bounds neighbours of `c` sharing a face, edge or corner
+    pub fn neighbors26(&self, c: Coord3) -> impl Iterator<Item = (Coord3, &T)> {
+        c.neighbors26()
+            .into_iter()
+            .filter_map(|n| Some((n, self.get(n)?)))
+    }
+
+    /// One `z=` layer after another with a blank line between them, y going down and x across
+    pub fn render(&self, mut f: impl FnMut(Coord3, &T) -> char) -> String {
+        let (x, y, z) = self.size();
+        let layer = AABB::from_range(Coord::ORIGIN, Coord::new(y, x));
+        let layers: Vec<_> = (0..z)
+            .map(|z| {
+                let cells = render::render(
+                    layer,
+                    |c| {
+                        let c = Coord3::new(c.col, c.row, z);
+                        f(c, &self[c])
+                    },
+                    None,
+                );
+                format!("z={z}\n{cells}")
+            })
+            .collect();
+        layers.join("\n\n")
+    }
+}
+
+impl Display for Grid3<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(|_, &c| c))
+    }
+}
+
+impl<T> Index<Coord3> for Grid3<T> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, index: Coord3) -> &Self::Output {
+        self.get(index).expect("Index out of Bounds")
+    }
+}
+impl<T> IndexMut<Coord3> for Grid3<T> {
+    #[track_caller]
+    fn index_mut(&mut self, index: Coord3) -> &mut Self::Output {
+        self.get_mut(index).expect("Index out of Bounds")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coords() {
+        let a = Coord3::new(1, 2, 3);
+        assert_eq!(a + Coord3::Z * 2, Coord3::new(1, 2, 5));
+        assert_eq!(a - Coord3::from((1, 1, 1)), Coord3::new(0, 1, 2));
+        assert_eq!(-a, Coord3::new(-1, -2, -3));
+        assert_eq!(a.dist_manhatten(Coord3::new(-1, 2, 0)), 5);
+
+        assert_eq!(a.neighbors6().len(), 6);
+        let n26 = a.neighbors26();
+        assert!(!n26.contains(&a));
+        assert!(n26
+            .iter()
+            .all(|n| (n.x - a.x).abs() <= 1 && (n.y - a.y).abs() <= 1 && (n.z - a.z).abs() <= 1));
+        for (i, n) in n26.iter().enumerate() {
+            assert!(!n26[i + 1..].contains(n));
+        }
+    }
+
+    #[test]
+    fn aabb() {
+        let aabb = AABB3::from_points([Coord3::new(1, -1, 0), Coord3::new(0, 1, 2)]);
+        assert_eq!(aabb.start(), Coord3::new(0, -1, 0));
+        assert_eq!(aabb.end(), Coord3::new(2, 2, 3));
+        assert!(aabb.contains(Coord3::new(1, 0, 2)));
+        assert!(!aabb.contains(Coord3::new(2, 0, 2)));
+        assert_eq!(aabb.coords().count(), 2 * 3 * 3);
+        assert_eq!(AABB3::new().coords().count(), 0);
+    }
+
+    #[test]
+    fn grid() {
+        let mut grid = Grid3::from_fn(2, 3, 4, |c| c.x + 10 * c.y + 100 * c.z);
+        assert_eq!(grid.size(), (2, 3, 4));
+        assert_eq!(grid[Coord3::new(1, 2, 3)], 321);
+        assert_eq!(grid.get(Coord3::new(2, 0, 0)), None);
+        assert_eq!(grid.coords().nth(2), Some(Coord3::new(0, 1, 0)));
+
+        grid[Coord3::ORIGIN] = -1;
+        assert_eq!(grid.neighbors6(Coord3::ORIGIN).count(), 3);
+        assert_eq!(grid.neighbors26(Coord3::ORIGIN).count(), 7);
+        assert_eq!(grid.neighbors26(Coord3::new(1, 1, 1)).count(), 17);
+
+        let empty = Grid3::filled(0, 5, 5, 0);
+        assert_eq!(empty.coords().count(), 0);
+        assert_eq!(empty.iter().count(), 0);
+    }
+
+    #[test]
+    fn iter_and_render() {
+        let grid = Grid3::from_fn(3, 2, 2, |c| if c.x == c.z { '#' } else { '.' });
+        assert!(grid.iter().all(|(c, &v)| grid[c] == v));
+        assert_eq!(grid.iter().filter(|&(_, &v)| v == '#').count(), 4);
+        assert_eq!(grid.to_string(), "z=0\n#..\n#..\n\nz=1\n.#.\n.#.");
+    }
+}
@@ -1,3 +1,19 @@
+pub mod cycle;
+pub mod direction;
 pub mod grid;
+pub mod grid3;
+pub mod offset;
+pub mod packed;
+pub mod point;
+pub mod region;
+pub mod render;
+pub mod search;
+pub mod sparse;
+pub mod tiled;
+pub mod transform;
+pub mod view;
 
-pub use {grid::Coord, grid::Grid};
+pub use {
+    direction::Direction, grid::Coord, grid::Grid, grid3::Coord3, grid3::Grid3, offset::OffsetGrid,
+    point::Point, sparse::SparseGrid,
+};
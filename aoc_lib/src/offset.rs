@@ -0,0 +1,239 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    grid::{Coord, Coords, Grid, AABB},
+    render::{self, Overlay},
+};
+
+/// A dense grid covering an arbitrary box, so coords can go negative without translating them by
+/// hand. It only grows when asked to, see `grow` and `get_mut_or_grow`
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct OffsetGrid<T> {
+    grid: Grid<T>,
+    // The coord of `grid[Coord::ORIGIN]`
+    origin: Coord,
+}
+
+impl<T> Default for OffsetGrid<T> {
+    fn default() -> Self {
+        Self::from_grid(Grid::default(), Coord::ORIGIN)
+    }
+}
+
+impl<T> OffsetGrid<T> {
+    pub fn new(bounds: AABB, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        let size = bounds.end() - bounds.start();
+        if size.row <= 0 || size.col <= 0 {
+            return Self::default();
+        }
+        Self::from_grid(
+            Grid::filled(size.row as usize, size.col as usize, fill),
+            bounds.start(),
+        )
+    }
+    /// Places the top left cell of `grid` at `origin`
+    pub fn from_grid(grid: Grid<T>, origin: Coord) -> Self {
+        Self { grid, origin }
+    }
+
+    /// The coord of the top left cell
+    pub fn origin(&self) -> Coord {
+        self.origin
+    }
+    pub fn bounds(&self) -> AABB {
+        let (rows, cols) = self.grid.size();
+        AABB::from_range(self.origin, self.origin + Coord::new(rows, cols))
+    }
+    /// The cells without their offset
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    pub fn contains(&self, c: Coord) -> bool {
+        self.get(c).is_some()
+    }
+    pub fn get(&self, c: Coord) -> Option<&T> {
+        self.grid.get(c - self.origin)
+    }
+    pub fn get_mut(&mut self, c: Coord) -> Option<&mut T> {
+        self.grid.get_mut(c - self.origin)
+    }
+
+    pub fn coords(&self) -> Coords {
+        self.bounds().coords()
+    }
+    /// Cells in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> + '_ {
+        self.coords().map(|c| (c, &self[c]))
+    }
+    pub fn neighbors4(&self, c: Coord) -> impl Iterator<Item = (Coord, &T)> + '_ {
+        let origin = self.origin;
+        self.grid
+            .neighbors4(c - origin)
+            .map(move |(n, v)| (n + origin, v))
+    }
+    pub fn neighbors8(&self, c: Coord) -> impl Iterator<Item = (Coord, &T)> + '_ {
+        let origin = self.origin;
+        self.grid
+            .neighbors8(c - origin)
+            .map(move |(n, v)| (n + origin, v))
+    }
+
+    /// Grows the grid to also cover `area`, new cells are `fill`. Cells keep their coords
+    pub fn grow(&mut self, area: AABB, fill: T)
+    where
+        T: Clone,
+    {
+        let (old, new) = (self.bounds(), area);
+        if self.grid.size() == (0, 0) {
+            *self = Self::new(new, fill);
+            return;
+        }
+        let start = Coord::new(
+            old.start().row.min(new.start().row),
+            old.start().col.min(new.start().col),
+        );
+        let end = Coord::new(
+            old.end().row.max(new.end().row),
+            old.end().col.max(new.end().col),
+        );
+        if (start, end) == (old.start(), old.end()) {
+            return;
+        }
+
+        // The old cells are a rectangle inside the new one, so both are visited in the same order
+        let (old_start, old_end) = (old.start() - start, old.end() - start);
+        let mut cells = std::mem::take(&mut self.grid).into_vec().into_iter();
+        let size = end - start;
+        self.grid = Grid::from_fn(size.row as usize, size.col as usize, |c| {
+            if (old_start.row..old_end.row).contains(&c.row)
+                && (old_start.col..old_end.col).contains(&c.col)
+            {
+                cells.next().unwrap()
+            } else {
+                fill.clone()
+            }
+        });
+        self.origin = start;
+    }
+    /// Like `get_mut`, but first grows the grid to reach `c` if it is outside. Growing at least
+    /// doubles the size in that direction, so walking off the edge step by step stays cheap
+    pub fn get_mut_or_grow(&mut self, c: Coord, fill: T) -> &mut T
+    where
+        T: Clone,
+    {
+        if !self.contains(c) {
+            let bounds = self.bounds();
+            let (start, end) = (bounds.start(), bounds.end());
+            let (rows, cols) = self.grid.size();
+            let mut area = AABB::new();
+            area.add(c);
+            if c.row < start.row {
+                area.add(Coord::new(c.row.min(start.row - rows), c.col));
+            } else if c.row >= end.row {
+                area.add(Coord::new(c.row.max(end.row - 1 + rows), c.col));
+            }
+            if c.col < start.col {
+                area.add(Coord::new(c.row, c.col.min(start.col - cols)));
+            } else if c.col >= end.col {
+                area.add(Coord::new(c.row, c.col.max(end.col - 1 + cols)));
+            }
+            self.grow(area, fill);
+        }
+        self.get_mut(c).unwrap()
+    }
+
+    pub fn render(&self, mut f: impl FnMut(Coord, &T) -> char) -> String {
+        let origin = self.origin;
+        self.grid.render(|c, v| f(c + origin, v))
+    }
+    /// The overlay uses the offset coords
+    pub fn render_overlay(
+        &self,
+        mut f: impl FnMut(Coord, &T) -> char,
+        overlay: &Overlay,
+    ) -> String {
+        render::render(self.bounds(), |c| f(c, &self[c]), Some(overlay))
+    }
+}
+
+impl<T> Index<Coord> for OffsetGrid<T> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, index: Coord) -> &Self::Output {
+        self.get(index).expect("Index out of Bounds")
+    }
+}
+impl<T> IndexMut<Coord> for OffsetGrid<T> {
+    #[track_caller]
+    fn index_mut(&mut self, index: Coord) -> &mut Self::Output {
+        self.get_mut(index).expect("Index out of Bounds")
+    }
+}
+
+impl Display for OffsetGrid<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.grid.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negative() {
+        let bounds = AABB::from_range(Coord::new(-1, -2), Coord::new(1, 1));
+        let mut grid = OffsetGrid::new(bounds, '.');
+        assert_eq!(grid.bounds(), bounds);
+        assert_eq!(grid.grid().size(), (2, 3));
+
+        grid[Coord::new(-1, -2)] = 'a';
+        grid[Coord::ORIGIN] = 'b';
+        assert_eq!(grid.get(Coord::new(1, 0)), None);
+        assert_eq!(grid.to_string(), "a..\n..b");
+
+        let coords: Vec<_> = grid.iter().filter(|(_, &v)| v != '.').collect();
+        assert_eq!(coords, [(Coord::new(-1, -2), &'a'), (Coord::ORIGIN, &'b')]);
+        let n: Vec<_> = grid
+            .neighbors4(Coord::new(-1, -1))
+            .map(|(c, _)| c)
+            .collect();
+        assert_eq!(n.len(), 3);
+        assert!(n.contains(&Coord::new(-1, -2)));
+    }
+
+    #[test]
+    fn grow() {
+        let mut grid = OffsetGrid::from_grid(Grid::parse_chars("ab\ncd"), Coord::ORIGIN);
+        grid.grow(AABB::from_range(Coord::new(-1, 1), Coord::new(1, 3)), '.');
+        assert_eq!(grid.origin(), Coord::new(-1, 0));
+        assert_eq!(grid.to_string(), "...\nab.\ncd.");
+        assert_eq!(grid[Coord::new(1, 1)], 'd');
+
+        // Already covered
+        grid.grow(AABB::from_range(Coord::ORIGIN, Coord::new(1, 1)), '.');
+        assert_eq!(grid.grid().size(), (3, 3));
+
+        *grid.get_mut_or_grow(Coord::new(0, -1), '.') = 'x';
+        assert_eq!(grid[Coord::new(0, -1)], 'x');
+        assert_eq!(grid[Coord::new(0, 0)], 'a');
+        // Grown by at least the old width
+        assert_eq!(grid.bounds().start(), Coord::new(-1, -3));
+
+        let mut empty = OffsetGrid::default();
+        *empty.get_mut_or_grow(Coord::new(-5, 5), 0) = 1;
+        assert_eq!(empty.bounds().start(), Coord::new(-5, 5));
+        assert_eq!(empty.iter().count(), 1);
+    }
+}
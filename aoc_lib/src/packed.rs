@@ -0,0 +1,426 @@
+use std::{
+    fmt::{Debug, Display},
+    marker::PhantomData,
+    ops::{BitAnd, BitOr, BitXor},
+};
+
+use crate::{
+    grid::{coord_to_index, Coord, Coords, Grid, AABB},
+    render,
+};
+
+/// Cell values that fit in a few bits. For an enum, `BITS` is enough bits to number the variants
+/// and `from_bits` only ever sees values returned by `to_bits`
+pub trait Pack: Copy {
+    const BITS: u32;
+    fn to_bits(self) -> u64;
+    fn from_bits(bits: u64) -> Self;
+}
+
+impl Pack for bool {
+    const BITS: u32 = 1;
+    fn to_bits(self) -> u64 {
+        self as u64
+    }
+    fn from_bits(bits: u64) -> Self {
+        bits != 0
+    }
+}
+impl Pack for u8 {
+    const BITS: u32 = 8;
+    fn to_bits(self) -> u64 {
+        self as u64
+    }
+    fn from_bits(bits: u64) -> Self {
+        bits as u8
+    }
+}
+
+/// A grid storing each cell in `T::BITS` bits. Cells never straddle two words, and the bits past
+/// the last cell are always zero
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct PackedGrid<T> {
+    rows: usize,
+    cols: usize,
+    words: Vec<u64>,
+    marker: PhantomData<T>,
+}
+
+/// One bit per cell, with whole-grid set operations done a word at a time
+pub type BitGrid = PackedGrid<bool>;
+
+impl<T> Default for PackedGrid<T> {
+    fn default() -> Self {
+        Self {
+            rows: 0,
+            cols: 0,
+            words: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Pack> PackedGrid<T> {
+    const PER_WORD: usize = 64 / T::BITS as usize;
+    const MASK: u64 = u64::MAX >> (64 - T::BITS);
+
+    /// Every cell is `T::from_bits(0)`
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            words: vec![0; (rows * cols).div_ceil(Self::PER_WORD)],
+            marker: PhantomData,
+        }
+    }
+    pub fn filled(rows: usize, cols: usize, v: T) -> Self {
+        Self::from_fn(rows, cols, |_| v)
+    }
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(Coord) -> T) -> Self {
+        let mut grid = Self::new(rows, cols);
+        for c in grid.coords() {
+            grid.set(c, f(c));
+        }
+        grid
+    }
+    pub fn from_grid(grid: &Grid<T>) -> Self {
+        let (rows, cols) = grid.size();
+        Self::from_fn(rows as usize, cols as usize, |c| grid[c])
+    }
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid::from_fn(self.rows, self.cols, |c| self.get(c).unwrap())
+    }
+
+    pub const fn size(&self) -> (i64, i64) {
+        (self.num_rows(), self.num_cols())
+    }
+    pub const fn num_rows(&self) -> i64 {
+        self.rows as i64
+    }
+    pub const fn num_cols(&self) -> i64 {
+        self.cols as i64
+    }
+    pub fn contains(&self, c: Coord) -> bool {
+        self.index(c).is_some()
+    }
+
+    fn index(&self, c: Coord) -> Option<usize> {
+        coord_to_index(c, self.num_rows(), self.num_cols())
+    }
+    // The word holding cell `index` and the shift of its lowest bit
+    fn locate(index: usize) -> (usize, u32) {
+        let word = index / Self::PER_WORD;
+        let shift = (index % Self::PER_WORD) as u32 * T::BITS;
+        (word, shift)
+    }
+
+    pub fn get(&self, c: Coord) -> Option<T> {
+        let (word, shift) = Self::locate(self.index(c)?);
+        Some(T::from_bits((self.words[word] >> shift) & Self::MASK))
+    }
+    /// Returns the old value
+    #[track_caller]
+    pub fn set(&mut self, c: Coord, v: T) -> T {
+        let index = self.index(c).expect("Index out of Bounds");
+        let (word, shift) = Self::locate(index);
+        let old = (self.words[word] >> shift) & Self::MASK;
+        self.words[word] &= !(Self::MASK << shift);
+        self.words[word] |= (v.to_bits() & Self::MASK) << shift;
+        T::from_bits(old)
+    }
+
+    pub fn bounds(&self) -> AABB {
+        AABB::from_range(Coord::ORIGIN, Coord::new(self.num_rows(), self.num_cols()))
+    }
+    pub fn coords(&self) -> Coords {
+        self.bounds().coords()
+    }
+    /// Cells in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, T)> + '_ {
+        self.coords().map(|c| (c, self.get(c).unwrap()))
+    }
+
+    #[track_caller]
+    pub fn row(&self, index: i64) -> PackedLine<'_, T> {
+        assert!(
+            (0..self.num_rows()).contains(&index),
+            "Row {index} out of bounds"
+        );
+        PackedLine {
+            grid: self,
+            first: Coord::new(index, 0),
+            step: Coord::E,
+            len: self.num_cols(),
+        }
+    }
+    #[track_caller]
+    pub fn col(&self, index: i64) -> PackedLine<'_, T> {
+        assert!(
+            (0..self.num_cols()).contains(&index),
+            "Column {index} out of bounds"
+        );
+        PackedLine {
+            grid: self,
+            first: Coord::new(0, index),
+            step: Coord::S,
+            len: self.num_rows(),
+        }
+    }
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = PackedLine<'_, T>> + '_ {
+        (0..self.num_rows()).map(|r| self.row(r))
+    }
+    pub fn cols(&self) -> impl DoubleEndedIterator<Item = PackedLine<'_, T>> + '_ {
+        (0..self.num_cols()).map(|c| self.col(c))
+    }
+
+    pub fn neighbors4(&self, c: Coord) -> impl Iterator<Item = (Coord, T)> + '_ {
+        c.neighbors4()
+            .into_iter()
+            .filter_map(|n| Some((n, self.get(n)?)))
+    }
+    pub fn neighbors8(&self, c: Coord) -> impl Iterator<Item = (Coord, T)> + '_ {
+        c.neighbors8()
+            .into_iter()
+            .filter_map(|n| Some((n, self.get(n)?)))
+    }
+
+    pub fn render(&self, mut f: impl FnMut(Coord, T) -> char) -> String {
+        render::render(self.bounds(), |c| f(c, self.get(c).unwrap()), None)
+    }
+}
+
+/// A row or column of a `PackedGrid`. Cells are returned by value since they have no address
+pub struct PackedLine<'grid, T> {
+    grid: &'grid PackedGrid<T>,
+    first: Coord,
+    step: Coord,
+    len: i64,
+}
+
+impl<'grid, T> Clone for PackedLine<'grid, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<'grid, T> Copy for PackedLine<'grid, T> {}
+
+impl<'grid, T: Pack> PackedLine<'grid, T> {
+    pub const fn len(&self) -> i64 {
+        self.len
+    }
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// The coord in the grid of cell `index`
+    pub fn coord_of(&self, index: i64) -> Coord {
+        self.first + self.step * index
+    }
+    pub fn get(&self, index: i64) -> Option<T> {
+        if !(0..self.len).contains(&index) {
+            return None;
+        }
+        self.grid.get(self.coord_of(index))
+    }
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = T> + 'grid {
+        let line = *self;
+        (0..self.len).map(move |i| line.get(i).unwrap())
+    }
+    /// The coords in the grid, in order
+    pub fn coords(&self) -> impl DoubleEndedIterator<Item = Coord> {
+        let (first, step) = (self.first, self.step);
+        (0..self.len).map(move |i| first + step * i)
+    }
+}
+
+impl<'grid, T: Pack + Debug> Debug for PackedLine<'grid, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl BitGrid {
+    /// Sets `c`, returning whether it was unset before
+    #[track_caller]
+    pub fn insert(&mut self, c: Coord) -> bool {
+        !self.set(c, true)
+    }
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+    /// Number of set cells
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+    /// The set cells in row-major order
+    pub fn ones(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.iter().filter(|&(_, v)| v).map(|(c, _)| c)
+    }
+
+    #[track_caller]
+    pub fn union_with(&mut self, other: &Self) {
+        self.zip_words(other, |a, b| a | b);
+    }
+    #[track_caller]
+    pub fn intersect_with(&mut self, other: &Self) {
+        self.zip_words(other, |a, b| a & b);
+    }
+    #[track_caller]
+    pub fn xor_with(&mut self, other: &Self) {
+        self.zip_words(other, |a, b| a ^ b);
+    }
+    #[track_caller]
+    pub fn difference_with(&mut self, other: &Self) {
+        self.zip_words(other, |a, b| a & !b);
+    }
+
+    #[track_caller]
+    fn zip_words(&mut self, other: &Self, f: impl Fn(u64, u64) -> u64) {
+        assert_eq!(self.size(), other.size(), "Grids have different sizes");
+        for (a, &b) in self.words.iter_mut().zip(&other.words) {
+            *a = f(*a, b);
+        }
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        let mut out = self.clone();
+        out.union_with(rhs);
+        out
+    }
+}
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        let mut out = self.clone();
+        out.intersect_with(rhs);
+        out
+    }
+}
+impl BitXor for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        let mut out = self.clone();
+        out.xor_with(rhs);
+        out
+    }
+}
+
+/// Set cells are `#`, unset ones `.`
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(|_, v| if v { '#' } else { '.' }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    enum Tile {
+        Empty,
+        Round,
+        Cube,
+    }
+
+    impl Pack for Tile {
+        const BITS: u32 = 2;
+        fn to_bits(self) -> u64 {
+            self as u64
+        }
+        fn from_bits(bits: u64) -> Self {
+            match bits {
+                0 => Tile::Empty,
+                1 => Tile::Round,
+                _ => Tile::Cube,
+            }
+        }
+    }
+
+    #[test]
+    fn bits() {
+        let mut a = BitGrid::new(3, 30);
+        assert_eq!(a.count(), 0);
+        assert!(a.insert(Coord::new(2, 29)));
+        assert!(!a.insert(Coord::new(2, 29)));
+        a.set(Coord::new(0, 0), true);
+        assert_eq!(a.get(Coord::new(2, 29)), Some(true));
+        assert_eq!(a.get(Coord::new(2, 30)), None);
+        assert_eq!(a.count(), 2);
+
+        let b = BitGrid::from_fn(3, 30, |c| c.col == 0);
+        assert_eq!((&a | &b).count(), 4);
+        assert_eq!((&a & &b).count(), 1);
+        assert_eq!((&a ^ &b).count(), 3);
+        a.difference_with(&b);
+        assert_eq!(a.ones().collect::<Vec<_>>(), [Coord::new(2, 29)]);
+
+        a.clear();
+        assert_eq!(a.count(), 0);
+        assert_eq!(BitGrid::filled(3, 30, true).count(), 90);
+    }
+
+    #[test]
+    fn enum_cells() {
+        let grid = Grid::parse_with("O.#\n.O.", |c| match c {
+            'O' => Tile::Round,
+            '#' => Tile::Cube,
+            _ => Tile::Empty,
+        });
+        let mut packed = PackedGrid::from_grid(&grid);
+        assert_eq!(packed.size(), (2, 3));
+        assert_eq!(packed.to_grid(), grid);
+        assert_eq!(packed.get(Coord::new(0, 2)), Some(Tile::Cube));
+
+        assert_eq!(packed.set(Coord::new(0, 2), Tile::Empty), Tile::Cube);
+        let round: Vec<_> = packed
+            .neighbors8(Coord::new(0, 1))
+            .filter(|&(_, t)| t == Tile::Round)
+            .map(|(c, _)| c)
+            .collect();
+        assert_eq!(round, [Coord::new(1, 1), Coord::new(0, 0)]);
+
+        let rendered = packed.render(|_, t| match t {
+            Tile::Empty => '.',
+            Tile::Round => 'O',
+            Tile::Cube => '#',
+        });
+        assert_eq!(rendered, "O..\n.O.");
+    }
+
+    #[test]
+    fn lines() {
+        let grid = BitGrid::from_fn(3, 70, |c| c.row == c.col || c.col == 69);
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.cols().count(), 70);
+
+        let row = grid.row(2);
+        assert_eq!(row.len(), 70);
+        assert_eq!(row.get(2), Some(true));
+        assert_eq!(row.get(70), None);
+        assert_eq!(row.iter().filter(|&v| v).count(), 2);
+        assert_eq!(row.coords().last(), Some(Coord::new(2, 69)));
+
+        let col = grid.col(1);
+        assert_eq!(format!("{col:?}"), "[false, true, false]");
+        assert_eq!(col.coord_of(2), Coord::new(2, 1));
+        assert!(grid.cols().next_back().unwrap().iter().all(|v| v));
+        assert_eq!(
+            grid.rows()
+                .map(|r| r.iter().filter(|&v| v).count())
+                .sum::<usize>(),
+            grid.count()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Column 3 out of bounds")]
+    fn line_out_of_bounds() {
+        PackedGrid::<u8>::new(2, 3).col(3);
+    }
+}
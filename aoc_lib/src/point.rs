@@ -0,0 +1,298 @@
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::{grid::Coord, grid3::Coord3};
+
+/// A coord with any number of axes, for when `Coord` and `Coord3` run out, e.g. 4D Conway cubes
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Point<const N: usize>(pub [i64; N]);
+
+impl<const N: usize> Default for Point<N> {
+    fn default() -> Self {
+        Self::ORIGIN
+    }
+}
+
+impl<const N: usize> Point<N> {
+    pub const ORIGIN: Self = Self([0; N]);
+
+    pub const fn new(axes: [i64; N]) -> Self {
+        Self(axes)
+    }
+    /// One step along `axis`
+    #[track_caller]
+    pub const fn unit(axis: usize) -> Self {
+        let mut axes = [0; N];
+        axes[axis] = 1;
+        Self(axes)
+    }
+
+    pub fn dist_manhatten(self, other: Self) -> i64 {
+        (0..N).map(|a| (self[a] - other[a]).abs()).sum()
+    }
+
+    /// The `2 * N` neighbours one step along a single axis, in the same order as `Coord3::DIRS6`
+    pub fn axis_neighbors(self) -> impl Iterator<Item = Self> {
+        (0..N).flat_map(move |a| [self + Self::unit(a), self - Self::unit(a)])
+    }
+    /// All `3^N - 1` neighbours, with the first axis changing fastest like `Coord3::DIRS26`
+    pub fn neighbors(self) -> impl Iterator<Item = Self> {
+        let count = 3usize.pow(N as u32);
+        // The offset with every digit 1 is the point itself
+        (0..count)
+            .filter(move |&i| i != count / 2)
+            .map(move |mut i| {
+                let mut p = self;
+                for a in 0..N {
+                    p[a] += (i % 3) as i64 - 1;
+                    i /= 3;
+                }
+                p
+            })
+    }
+}
+
+impl<const N: usize> From<[i64; N]> for Point<N> {
+    fn from(axes: [i64; N]) -> Self {
+        Self(axes)
+    }
+}
+impl From<Coord> for Point<2> {
+    fn from(c: Coord) -> Self {
+        Self([c.row, c.col])
+    }
+}
+impl From<Point<2>> for Coord {
+    fn from(Point([row, col]): Point<2>) -> Self {
+        Coord::new(row, col)
+    }
+}
+impl From<Coord3> for Point<3> {
+    fn from(c: Coord3) -> Self {
+        Self([c.x, c.y, c.z])
+    }
+}
+impl From<Point<3>> for Coord3 {
+    fn from(Point([x, y, z]): Point<3>) -> Self {
+        Coord3::new(x, y, z)
+    }
+}
+
+impl<const N: usize> Index<usize> for Point<N> {
+    type Output = i64;
+
+    #[track_caller]
+    fn index(&self, axis: usize) -> &Self::Output {
+        &self.0[axis]
+    }
+}
+impl<const N: usize> IndexMut<usize> for Point<N> {
+    #[track_caller]
+    fn index_mut(&mut self, axis: usize) -> &mut Self::Output {
+        &mut self.0[axis]
+    }
+}
+
+impl<const N: usize> Add for Point<N> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+impl<const N: usize> Sub for Point<N> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+impl<const N: usize> AddAssign for Point<N> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a += b;
+        }
+    }
+}
+impl<const N: usize> SubAssign for Point<N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a -= b;
+        }
+    }
+}
+impl<const N: usize> Mul<i64> for Point<N> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: i64) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+impl<const N: usize> MulAssign<i64> for Point<N> {
+    fn mul_assign(&mut self, rhs: i64) {
+        for a in &mut self.0 {
+            *a *= rhs;
+        }
+    }
+}
+impl<const N: usize> Neg for Point<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self * -1
+    }
+}
+
+/// The N-dimensional version of `AABB`
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct AABBN<const N: usize> {
+    start: Point<N>,
+    end: Point<N>,
+}
+
+impl<const N: usize> Default for AABBN<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> AABBN<N> {
+    pub const fn new() -> Self {
+        AABBN {
+            start: Point([i64::MAX; N]),
+            end: Point([i64::MIN; N]),
+        }
+    }
+
+    /// Covers `start` up to but not including `end`
+    pub const fn from_range(start: Point<N>, end: Point<N>) -> Self {
+        AABBN { start, end }
+    }
+
+    pub fn from_points(points: impl IntoIterator<Item = Point<N>>) -> Self {
+        let mut aabb = Self::new();
+        for p in points {
+            aabb.add(p);
+        }
+        aabb
+    }
+
+    pub fn add(&mut self, p: Point<N>) {
+        for a in 0..N {
+            self.start[a] = self.start[a].min(p[a]);
+            self.end[a] = self.end[a].max(p[a] + 1);
+        }
+    }
+    /// Grows by `n` on every side, as a Conway step can reach one cell further out
+    pub fn expand(&self, n: i64) -> Self {
+        let grow = Point([n; N]);
+        Self::from_range(self.start - grow, self.end + grow)
+    }
+
+    pub const fn start(&self) -> Point<N> {
+        self.start
+    }
+    pub const fn end(&self) -> Point<N> {
+        self.end
+    }
+
+    pub fn contains(&self, p: Point<N>) -> bool {
+        (0..N).all(|a| (self.start[a]..self.end[a]).contains(&p[a]))
+    }
+
+    /// Every point inside, with the first axis changing fastest
+    pub fn coords(&self) -> impl Iterator<Item = Point<N>> {
+        let (start, end) = (self.start, self.end);
+        // Saturating as an empty box spans from `i64::MAX` down to `i64::MIN`
+        let count = (0..N)
+            .map(|a| end[a].saturating_sub(start[a]).max(0) as usize)
+            .product();
+        (0..count).map(move |mut i| {
+            let mut p = start;
+            for a in 0..N {
+                let len = (end[a] - start[a]) as usize;
+                p[a] += (i % len) as i64;
+                i /= len;
+            }
+            p
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use super::*;
+
+    #[test]
+    fn points() {
+        let a = Point::new([1, 2, 3, 4]);
+        assert_eq!(a + Point::unit(3) * 2, Point::new([1, 2, 3, 6]));
+        assert_eq!(a - Point::from([1, 1, 1, 1]), Point::new([0, 1, 2, 3]));
+        assert_eq!(-a, Point::new([-1, -2, -3, -4]));
+        assert_eq!(a.dist_manhatten(Point::ORIGIN), 10);
+        assert_eq!(a[2], 3);
+
+        assert_eq!(
+            Coord::from(Point::from(Coord::new(3, -1))),
+            Coord::new(3, -1)
+        );
+        let c = Coord3::new(1, 2, 3);
+        assert_eq!(Coord3::from(Point::from(c)), c);
+    }
+
+    #[test]
+    fn neighbors() {
+        let a = Point::new([1, 2, 3, 4]);
+        assert_eq!(a.axis_neighbors().count(), 8);
+        let n: HashSet<_> = a.neighbors().collect();
+        assert_eq!(n.len(), 80);
+        assert!(!n.contains(&a));
+        assert!(n.iter().all(|&p| (0..4).all(|i| (p[i] - a[i]).abs() <= 1)));
+
+        // Same order as the 3D constants
+        let origin = Point::from(Coord3::ORIGIN);
+        let dirs6: Vec<Coord3> = origin.axis_neighbors().map(Coord3::from).collect();
+        assert_eq!(dirs6, Coord3::DIRS6);
+        let dirs26: Vec<Coord3> = origin.neighbors().map(Coord3::from).collect();
+        assert_eq!(dirs26, Coord3::DIRS26);
+    }
+
+    #[test]
+    fn aabb() {
+        let aabb = AABBN::from_points([Point::new([1, -1, 0, 0]), Point::new([0, 1, 2, 0])]);
+        assert_eq!(aabb.start(), Point::new([0, -1, 0, 0]));
+        assert_eq!(aabb.end(), Point::new([2, 2, 3, 1]));
+        assert!(aabb.contains(Point::new([1, 0, 2, 0])));
+        assert!(!aabb.contains(Point::new([1, 0, 2, 1])));
+        assert_eq!(aabb.coords().count(), 2 * 3 * 3);
+        assert_eq!(aabb.coords().nth(1), Some(Point::new([1, -1, 0, 0])));
+        assert!(aabb.coords().all(|p| aabb.contains(p)));
+        assert_eq!(aabb.expand(1).coords().count(), 4 * 5 * 5 * 3);
+        assert_eq!(AABBN::<4>::new().coords().count(), 0);
+    }
+
+    #[test]
+    fn conway_4d() {
+        // The glider from 2020 day 17, six cycles in four dimensions
+        let mut active: HashSet<Point<4>> = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]
+            .into_iter()
+            .map(|(x, y)| Point::new([x, y, 0, 0]))
+            .collect();
+        for _ in 0..6 {
+            let mut counts = HashMap::new();
+            for n in active.iter().flat_map(|p| p.neighbors()) {
+                *counts.entry(n).or_insert(0) += 1;
+            }
+            active = counts
+                .into_iter()
+                .filter(|&(p, n)| n == 3 || (n == 2 && active.contains(&p)))
+                .map(|(p, _)| p)
+                .collect();
+        }
+        assert_eq!(active.len(), 848);
+    }
+}
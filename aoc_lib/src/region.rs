@@ -0,0 +1,228 @@
+use std::collections::HashSet;
+
+use crate::grid::{Coord, Grid, AABB};
+
+/// Which region of `Grid::connected_components` a cell belongs to, numbered in row-major order
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Default)]
+pub struct RegionId(pub usize);
+
+/// A set of coords, usually orthogonally connected
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Region {
+    cells: HashSet<Coord>,
+}
+
+impl Region {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn contains(&self, c: Coord) -> bool {
+        self.cells.contains(&c)
+    }
+    pub fn insert(&mut self, c: Coord) -> bool {
+        self.cells.insert(c)
+    }
+    pub fn iter(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.cells.iter().copied()
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+    /// Cell edges that don't touch another cell of the region
+    pub fn perimeter(&self) -> usize {
+        self.iter()
+            .flat_map(Coord::neighbors4)
+            .filter(|&n| !self.contains(n))
+            .count()
+    }
+    /// Straight sides of the outline, holes included
+    pub fn sides(&self) -> usize {
+        // A polygon has as many sides as corners, so count corners instead
+        let mut corners = 0;
+        for c in self.iter() {
+            for (a, b) in [
+                (Coord::N, Coord::E),
+                (Coord::E, Coord::S),
+                (Coord::S, Coord::W),
+                (Coord::W, Coord::N),
+            ] {
+                let (has_a, has_b) = (self.contains(c + a), self.contains(c + b));
+                let outer = !has_a && !has_b;
+                let inner = has_a && has_b && !self.contains(c + a + b);
+                if outer || inner {
+                    corners += 1;
+                }
+            }
+        }
+        corners
+    }
+    pub fn bounds(&self) -> AABB {
+        AABB::from_points(self.iter())
+    }
+}
+
+impl FromIterator<Coord> for Region {
+    fn from_iter<I: IntoIterator<Item = Coord>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoIterator for Region {
+    type Item = Coord;
+    type IntoIter = std::collections::hash_set::IntoIter<Coord>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.into_iter()
+    }
+}
+
+/// Every coord reachable from `start` in orthogonal steps through coords passing `f`.
+///
+/// Nothing bounds the fill, so `f` has to reject everything outside the area of interest
+pub fn flood(start: Coord, mut f: impl FnMut(Coord) -> bool) -> Region {
+    let mut region = Region::new();
+    let mut stack = vec![start];
+    while let Some(c) = stack.pop() {
+        if region.contains(c) || !f(c) {
+            continue;
+        }
+        region.insert(c);
+        stack.extend(c.neighbors4());
+    }
+    region
+}
+
+impl<T> Grid<T> {
+    /// The in bounds cells connected to `start` whose values pass `f`
+    pub fn flood_fill(&self, start: Coord, mut f: impl FnMut(&T) -> bool) -> Region {
+        flood(start, |c| self.get(c).is_some_and(&mut f))
+    }
+
+    /// Labels each group of orthogonally connected cells that `same` considers equal
+    pub fn connected_components(&self, mut same: impl FnMut(&T, &T) -> bool) -> Grid<RegionId> {
+        let (rows, cols) = (self.num_rows() as usize, self.num_cols() as usize);
+        let mut ids: Grid<Option<RegionId>> = Grid::filled(rows, cols, None);
+        let mut next = 0;
+        let mut stack = Vec::new();
+
+        for start in self.coords() {
+            if ids[start].is_some() {
+                continue;
+            }
+            let id = RegionId(next);
+            next += 1;
+
+            ids[start] = Some(id);
+            stack.push(start);
+            while let Some(c) = stack.pop() {
+                for (n, v) in self.neighbors4(c) {
+                    if ids[n].is_none() && same(&self[c], v) {
+                        ids[n] = Some(id);
+                        stack.push(n);
+                    }
+                }
+            }
+        }
+
+        Grid::from_fn(rows, cols, |c| ids[c].unwrap())
+    }
+}
+
+impl Grid<RegionId> {
+    /// The cells of each region, indexed by `RegionId`
+    pub fn regions(&self) -> Vec<Region> {
+        let mut regions = Vec::new();
+        for c in self.coords() {
+            let RegionId(id) = self[c];
+            if id >= regions.len() {
+                regions.resize_with(id + 1, Region::new);
+            }
+            regions[id].insert(c);
+        }
+        regions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn components() {
+        let grid = Grid::parse_chars(
+            "
+            AAAA
+            BBCD
+            BBCC
+            EEEC
+            ",
+        );
+        let ids = grid.connected_components(|a, b| a == b);
+        assert_eq!(ids[Coord::new(0, 0)], RegionId(0));
+        assert_eq!(ids[Coord::new(2, 1)], RegionId(1));
+        assert_eq!(ids[Coord::new(3, 3)], ids[Coord::new(1, 2)]);
+
+        let regions = ids.regions();
+        let metrics: Vec<_> = regions
+            .iter()
+            .map(|r| (r.area(), r.perimeter(), r.sides()))
+            .collect();
+        assert_eq!(
+            metrics,
+            [(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)]
+        );
+
+        let c = &regions[ids[Coord::new(1, 2)].0];
+        assert_eq!(
+            c.bounds(),
+            AABB::from_range(Coord::new(1, 2), Coord::new(4, 4))
+        );
+    }
+
+    #[test]
+    fn holes() {
+        let grid = Grid::parse_chars(
+            "
+            OOOOO
+            OXOXO
+            OOOOO
+            OXOXO
+            OOOOO
+            ",
+        );
+        let ids = grid.connected_components(|a, b| a == b);
+        let outer = &ids.regions()[0];
+        assert_eq!(outer.area(), 21);
+        assert_eq!(outer.perimeter(), 36);
+        assert_eq!(outer.sides(), 20);
+    }
+
+    #[test]
+    fn fill() {
+        let grid = Grid::parse_chars(
+            "
+            ..#..
+            ..#..
+            ###..
+            .....
+            ",
+        );
+        let region = grid.flood_fill(Coord::ORIGIN, |&c| c == '.');
+        assert_eq!(region.area(), 4);
+        assert!(!region.contains(Coord::new(0, 3)));
+
+        let region = grid.flood_fill(Coord::new(0, 4), |&c| c == '.');
+        assert_eq!(region.area(), 11);
+
+        assert_eq!(grid.flood_fill(Coord::new(0, 2), |&c| c == '.').area(), 0);
+
+        // Big enough that a recursive fill would overflow the stack
+        let open = flood(Coord::ORIGIN, |c| {
+            (0..300).contains(&c.row) && (0..300).contains(&c.col)
+        });
+        assert_eq!(open.area(), 90_000);
+    }
+}
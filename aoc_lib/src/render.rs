@@ -0,0 +1,91 @@
+use std::{collections::HashMap, fmt::Write};
+
+use crate::grid::{Coord, AABB};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    const fn ansi(self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum Highlight {
+    Color(Color),
+    Char(char),
+}
+
+/// Highlights drawn over a rendered grid, a later highlight of a coord replaces an earlier one
+#[derive(Clone, Default, Debug)]
+pub struct Overlay {
+    cells: HashMap<Coord, Highlight>,
+}
+
+impl Overlay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn color(self, coords: impl IntoIterator<Item = Coord>, color: Color) -> Self {
+        self.highlight(coords, Highlight::Color(color))
+    }
+    pub fn replace(self, coords: impl IntoIterator<Item = Coord>, c: char) -> Self {
+        self.highlight(coords, Highlight::Char(c))
+    }
+    pub fn highlight(mut self, coords: impl IntoIterator<Item = Coord>, h: Highlight) -> Self {
+        for c in coords {
+            self.cells.insert(c, h);
+        }
+        self
+    }
+    pub fn get(&self, coord: Coord) -> Option<Highlight> {
+        self.cells.get(&coord).copied()
+    }
+
+    fn push(&self, out: &mut String, coord: Coord, c: char) {
+        match self.get(coord) {
+            None => out.push(c),
+            Some(Highlight::Char(h)) => out.push(h),
+            Some(Highlight::Color(color)) => {
+                write!(out, "\x1b[{}m{c}\x1b[0m", color.ansi()).unwrap()
+            }
+        }
+    }
+}
+
+// One line per row of `bounds` without a trailing newline
+pub(crate) fn render(
+    bounds: AABB,
+    mut cell: impl FnMut(Coord) -> char,
+    overlay: Option<&Overlay>,
+) -> String {
+    let mut out = String::new();
+    for (i, row) in bounds.rows().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        for coord in row {
+            let c = cell(coord);
+            match overlay {
+                Some(overlay) => overlay.push(&mut out, coord, c),
+                None => out.push(c),
+            }
+        }
+    }
+    out
+}
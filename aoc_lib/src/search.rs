@@ -0,0 +1,299 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// The cheapest way found to a goal, `states` runs from the start to the goal inclusive
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Path<S, C> {
+    pub cost: C,
+    pub states: Vec<S>,
+}
+
+impl<S, C> Path<S, C> {
+    pub fn start(&self) -> &S {
+        self.states.first().unwrap()
+    }
+    pub fn goal(&self) -> &S {
+        self.states.last().unwrap()
+    }
+}
+
+/// Costs are summed from `C::default()`, so any unsigned or signed integer works
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+// Every state seen so far, with the parent it was first reached from
+struct Seen<S> {
+    states: Vec<S>,
+    parents: Vec<usize>,
+    index: HashMap<S, usize>,
+}
+
+impl<S: Clone + Eq + Hash> Seen<S> {
+    fn new() -> Self {
+        Self {
+            states: Vec::new(),
+            parents: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    // The index of `s` and whether it is new
+    fn insert(&mut self, s: S, parent: usize) -> (usize, bool) {
+        match self.index.entry(s) {
+            Entry::Occupied(e) => (*e.get(), false),
+            Entry::Vacant(e) => {
+                let i = self.states.len();
+                self.states.push(e.key().clone());
+                self.parents.push(parent);
+                e.insert(i);
+                (i, true)
+            }
+        }
+    }
+
+    fn path(&self, mut i: usize) -> Vec<S> {
+        let mut path = vec![self.states[i].clone()];
+        while self.parents[i] != usize::MAX {
+            i = self.parents[i];
+            path.push(self.states[i].clone());
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Fewest steps from any of `starts` to a state passing `goal`
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut seen = Seen::new();
+    let mut queue = VecDeque::new();
+    for s in starts {
+        let (i, new) = seen.insert(s, usize::MAX);
+        if new {
+            queue.push_back((i, 0));
+        }
+    }
+
+    while let Some((i, steps)) = queue.pop_front() {
+        if goal(&seen.states[i]) {
+            return Some(Path {
+                cost: steps,
+                states: seen.path(i),
+            });
+        }
+        for next in successors(&seen.states[i].clone()) {
+            let (j, new) = seen.insert(next, i);
+            if new {
+                queue.push_back((j, steps + 1));
+            }
+        }
+    }
+    None
+}
+
+/// Cheapest path from any of `starts` to a state passing `goal`, costs must not be negative
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, successors, goal, |_| C::default())
+}
+
+/// Dijkstra guided by `heuristic`, which must never overestimate the cost left to a goal
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+    mut heuristic: impl FnMut(&S) -> C,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut seen = Seen::new();
+    let mut costs = Vec::new();
+    let mut queue = BinaryHeap::new();
+    for s in starts {
+        let h = heuristic(&s);
+        let (i, new) = seen.insert(s, usize::MAX);
+        if new {
+            costs.push(C::default());
+            queue.push(Reverse((h, C::default(), i)));
+        }
+    }
+
+    while let Some(Reverse((_, cost, i))) = queue.pop() {
+        // A cheaper way here was already expanded
+        if cost > costs[i] {
+            continue;
+        }
+        if goal(&seen.states[i]) {
+            return Some(Path {
+                cost,
+                states: seen.path(i),
+            });
+        }
+
+        for (next, step) in successors(&seen.states[i].clone()) {
+            let next_cost = cost + step;
+            let h = heuristic(&next);
+            let (j, new) = seen.insert(next, i);
+            if new {
+                costs.push(next_cost);
+            } else if next_cost < costs[j] {
+                costs[j] = next_cost;
+                seen.parents[j] = i;
+            } else {
+                continue;
+            }
+            queue.push(Reverse((next_cost + h, next_cost, j)));
+        }
+    }
+    None
+}
+
+/// Cheapest cost from `starts` to every reachable state
+pub fn dijkstra_all<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+) -> HashMap<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut costs = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    for s in starts {
+        costs.insert(s.clone(), C::default());
+        queue.push(Reverse((C::default(), Node(s))));
+    }
+
+    while let Some(Reverse((cost, Node(s)))) = queue.pop() {
+        if costs.get(&s).is_some_and(|&best| cost > best) {
+            continue;
+        }
+        for (next, step) in successors(&s) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|&best| next_cost < best) {
+                costs.insert(next.clone(), next_cost);
+                queue.push(Reverse((next_cost, Node(next))));
+            }
+        }
+    }
+    costs
+}
+
+// Lets states without `Ord` sit in the heap, only the cost is compared
+struct Node<S>(S);
+
+impl<S> PartialEq for Node<S> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+impl<S> Eq for Node<S> {}
+impl<S> PartialOrd for Node<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<S> Ord for Node<S> {
+    fn cmp(&self, _: &Self) -> std::cmp::Ordering {
+        std::cmp::Ordering::Equal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Coord, Grid};
+
+    const MAZE: &str = "
+        S.#.....
+        .##.###.
+        ....#...
+        .####.#.
+        ......#E
+    ";
+
+    fn find(grid: &Grid<char>, c: char) -> Coord {
+        grid.coords().find(|&p| grid[p] == c).unwrap()
+    }
+
+    #[test]
+    fn shortest_paths() {
+        let grid = Grid::parse_chars(MAZE);
+        let (start, end) = (find(&grid, 'S'), find(&grid, 'E'));
+        let open = |c: &Coord| -> Vec<Coord> {
+            grid.neighbors4_where(*c, |&v| v != '#')
+                .map(|(c, _)| c)
+                .collect()
+        };
+
+        let path = bfs([start], open, |&c| c == end).unwrap();
+        assert_eq!(path.cost, 15);
+        assert_eq!(path.states.len(), 16);
+        assert_eq!((*path.start(), *path.goal()), (start, end));
+        for w in path.states.windows(2) {
+            assert_eq!(w[0].dist_manhatten(w[1]), 1);
+        }
+
+        let weighted = |c: &Coord| open(c).into_iter().map(|c| (c, 1u64));
+        let path = dijkstra([start], weighted, |&c| c == end).unwrap();
+        assert_eq!(path.cost, 15);
+
+        let h = |c: &Coord| c.dist_manhatten(end) as u64;
+        let path = astar([start], weighted, |&c| c == end, h).unwrap();
+        assert_eq!(path.cost, 15);
+        assert_eq!(path.states.len(), 16);
+
+        // Walls are never successors
+        assert!(bfs([start], open, |&c| grid[c] == '#').is_none());
+
+        let costs = dijkstra_all([start], weighted);
+        assert_eq!(costs[&end], 15);
+        assert_eq!(
+            costs.len(),
+            grid.coords().filter(|&c| grid[c] != '#').count()
+        );
+    }
+
+    #[test]
+    fn weighted() {
+        // Going round is cheaper than the direct edge
+        let edges = |&n: &u8| -> Vec<(u8, i64)> {
+            match n {
+                0 => vec![(1, 10), (2, 1)],
+                2 => vec![(3, 1)],
+                3 => vec![(1, 1)],
+                _ => vec![],
+            }
+        };
+        let path = dijkstra([0], edges, |&n| n == 1).unwrap();
+        assert_eq!(path.cost, 3);
+        assert_eq!(path.states, [0, 2, 3, 1]);
+
+        // Several starts race each other
+        let path = dijkstra([0, 3], edges, |&n| n == 1).unwrap();
+        assert_eq!(path.states, [3, 1]);
+    }
+}
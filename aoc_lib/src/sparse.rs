@@ -0,0 +1,255 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    grid::{Coord, Grid, AABB},
+    render::{self, Overlay},
+};
+
+/// Cells at arbitrary, possibly negative, coords. The bounds grow to cover every cell inserted
+/// and shrink again as cells on the edge are removed
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Coord, T>,
+    bounds: AABB,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: AABB::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+    /// The smallest box around every cell, empty when there are no cells
+    pub fn bounds(&self) -> AABB {
+        self.bounds
+    }
+
+    pub fn contains(&self, c: Coord) -> bool {
+        self.cells.contains_key(&c)
+    }
+    pub fn get(&self, c: Coord) -> Option<&T> {
+        self.cells.get(&c)
+    }
+    pub fn get_mut(&mut self, c: Coord) -> Option<&mut T> {
+        self.cells.get_mut(&c)
+    }
+    /// Returns the value that was at `c` before
+    pub fn insert(&mut self, c: Coord, value: T) -> Option<T> {
+        self.bounds.add(c);
+        self.cells.insert(c, value)
+    }
+    pub fn remove(&mut self, c: Coord) -> Option<T> {
+        let value = self.cells.remove(&c)?;
+
+        let (start, end) = (self.bounds.start(), self.bounds.end());
+        let on_edge = c.row == start.row
+            || c.col == start.col
+            || c.row == end.row - 1
+            || c.col == end.col - 1;
+        if on_edge {
+            self.bounds = AABB::from_points(self.cells.keys().copied());
+        }
+        Some(value)
+    }
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// The orthogonal neighbours of `c` that have a value
+    pub fn neighbors4(&self, c: Coord) -> impl Iterator<Item = (Coord, &T)> + '_ {
+        c.neighbors4()
+            .into_iter()
+            .filter_map(|n| Some((n, self.get(n)?)))
+    }
+    /// The orthogonal and diagonal neighbours of `c` that have a value
+    pub fn neighbors8(&self, c: Coord) -> impl Iterator<Item = (Coord, &T)> + '_ {
+        c.neighbors8()
+            .into_iter()
+            .filter_map(|n| Some((n, self.get(n)?)))
+    }
+
+    /// Coords with a value in row-major order
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let mut coords: Vec<_> = self.cells.keys().copied().collect();
+        coords.sort_unstable_by_key(|c| (c.row, c.col));
+        coords.into_iter()
+    }
+    /// Cells in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> + '_ {
+        self.coords().map(|c| (c, &self.cells[&c]))
+    }
+    /// Cells in no particular order, without the cost of sorting
+    pub fn iter_unordered(&self) -> impl Iterator<Item = (Coord, &T)> + '_ {
+        self.cells.iter().map(|(&c, v)| (c, v))
+    }
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> + '_ {
+        self.cells.values_mut()
+    }
+
+    /// Draws every coord of the bounds, `f` gets `None` for coords without a value
+    pub fn render(&self, mut f: impl FnMut(Coord, Option<&T>) -> char) -> String {
+        render::render(self.bounds, |c| f(c, self.get(c)), None)
+    }
+    pub fn render_overlay(
+        &self,
+        mut f: impl FnMut(Coord, Option<&T>) -> char,
+        overlay: &Overlay,
+    ) -> String {
+        render::render(self.bounds, |c| f(c, self.get(c)), Some(overlay))
+    }
+
+    /// A dense copy of the bounds with `fill` in the gaps. The grid starts at `bounds().start()`,
+    /// so sparse coord `c` ends up at `c - bounds().start()`
+    pub fn to_grid(&self, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        if self.is_empty() {
+            return Grid::default();
+        }
+        let start = self.bounds.start();
+        let size = self.bounds.end() - start;
+        Grid::from_fn(size.row as usize, size.col as usize, |c| {
+            self.get(c + start).unwrap_or(&fill).clone()
+        })
+    }
+    /// The cells of `grid` that pass `f`, at the same coords
+    pub fn from_grid_where(grid: Grid<T>, mut f: impl FnMut(&T) -> bool) -> Self {
+        let coords = grid.coords();
+        grid.into_vec()
+            .into_iter()
+            .zip(coords)
+            .filter(|(v, _)| f(v))
+            .map(|(v, c)| (c, v))
+            .collect()
+    }
+}
+
+impl<T> From<Grid<T>> for SparseGrid<T> {
+    fn from(grid: Grid<T>) -> Self {
+        Self::from_grid_where(grid, |_| true)
+    }
+}
+
+impl<T> FromIterator<(Coord, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Coord, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Coord, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Coord, T)>>(&mut self, iter: I) {
+        for (c, v) in iter {
+            self.insert(c, v);
+        }
+    }
+}
+
+impl<T> Index<Coord> for SparseGrid<T> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, index: Coord) -> &Self::Output {
+        self.get(index).expect("No value at coord")
+    }
+}
+impl<T> IndexMut<Coord> for SparseGrid<T> {
+    #[track_caller]
+    fn index_mut(&mut self, index: Coord) -> &mut Self::Output {
+        self.get_mut(index).expect("No value at coord")
+    }
+}
+
+/// Gaps are drawn as `.`
+impl Display for SparseGrid<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(|_, c| c.copied().unwrap_or('.')))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), AABB::new());
+
+        grid.insert(Coord::new(-2, 3), 'a');
+        grid.insert(Coord::new(1, -1), 'b');
+        grid.insert(Coord::new(0, 0), 'c');
+        assert_eq!(
+            grid.bounds(),
+            AABB::from_range(Coord::new(-2, -1), Coord::new(2, 4))
+        );
+        assert_eq!(grid.to_string(), "....a\n.....\n.c...\nb....");
+
+        assert_eq!(grid.remove(Coord::new(-2, 3)), Some('a'));
+        assert_eq!(grid.remove(Coord::new(-2, 3)), None);
+        assert_eq!(
+            grid.bounds(),
+            AABB::from_range(Coord::new(0, -1), Coord::new(2, 1))
+        );
+        assert_eq!(grid.to_string(), ".c\nb.");
+
+        grid.clear();
+        assert!(grid.is_empty());
+        assert_eq!(grid.to_string(), "");
+    }
+
+    #[test]
+    fn access() {
+        let mut grid: SparseGrid<u32> = [(Coord::new(5, 5), 1), (Coord::new(5, 6), 2)]
+            .into_iter()
+            .collect();
+        grid[Coord::new(5, 6)] += 10;
+        assert_eq!(grid.get(Coord::new(5, 6)), Some(&12));
+        assert!(grid.contains(Coord::new(5, 5)));
+        assert!(!grid.contains(Coord::new(0, 0)));
+
+        grid.insert(Coord::new(4, 4), 3);
+        let n4: Vec<_> = grid.neighbors4(Coord::new(5, 5)).collect();
+        assert_eq!(n4, [(Coord::new(5, 6), &12)]);
+        assert_eq!(grid.neighbors8(Coord::new(5, 5)).count(), 2);
+
+        grid.insert(Coord::new(6, 0), 4);
+        let order: Vec<_> = grid.iter().map(|(_, &v)| v).collect();
+        assert_eq!(order, [3, 1, 12, 4]);
+    }
+
+    #[test]
+    fn dense() {
+        let dense = Grid::parse_chars("#.\n.#");
+        let sparse = SparseGrid::from_grid_where(dense.clone(), |&c| c == '#');
+        assert_eq!(sparse.len(), 2);
+        assert_eq!(sparse.to_grid('.'), dense);
+        assert_eq!(SparseGrid::from(dense.clone()).to_grid('x'), dense);
+
+        // Shifted so the bounds start at the origin
+        let sparse: SparseGrid<_> = [(Coord::new(-1, -1), 1), (Coord::new(0, 1), 2)]
+            .into_iter()
+            .collect();
+        assert_eq!(sparse.to_grid(0), Grid::from([[1, 0, 0], [0, 0, 2]]));
+    }
+}
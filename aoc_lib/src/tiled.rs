@@ -0,0 +1,197 @@
+use std::ops::Index;
+
+use crate::{
+    grid::{Coord, Coords, Grid, AABB},
+    render,
+};
+
+/// Wrapping access, for grids that repeat forever in every direction
+impl<T> Grid<T> {
+    /// The in bounds coord that `c` lands on once the grid is repeated, `None` for an empty grid
+    pub fn wrap(&self, c: Coord) -> Option<Coord> {
+        let (rows, cols) = self.size();
+        if rows == 0 || cols == 0 {
+            return None;
+        }
+        Some(Coord::new(c.row.rem_euclid(rows), c.col.rem_euclid(cols)))
+    }
+    pub fn get_wrapping(&self, c: Coord) -> Option<&T> {
+        self.get(self.wrap(c)?)
+    }
+    pub fn get_wrapping_mut(&mut self, c: Coord) -> Option<&mut T> {
+        let c = self.wrap(c)?;
+        self.get_mut(c)
+    }
+
+    /// All 4 neighbours of `c`. The coords are not wrapped, only the values are read wrapped
+    pub fn neighbors4_wrapping(&self, c: Coord) -> impl Iterator<Item = (Coord, &T)> {
+        c.neighbors4()
+            .into_iter()
+            .filter_map(|n| Some((n, self.get_wrapping(n)?)))
+    }
+    /// All 8 neighbours of `c`. The coords are not wrapped, only the values are read wrapped
+    pub fn neighbors8_wrapping(&self, c: Coord) -> impl Iterator<Item = (Coord, &T)> {
+        c.neighbors8()
+            .into_iter()
+            .filter_map(|n| Some((n, self.get_wrapping(n)?)))
+    }
+
+    /// The grid with `n` copies of itself on every side, so `(2n + 1)²` tiles in total. The
+    /// original keeps its coords in the middle tile
+    pub fn tiled(&self, n: usize) -> TiledView<'_, T> {
+        TiledView { grid: self, n }
+    }
+}
+
+/// A grid repeated around itself without copying, see `Grid::tiled`
+pub struct TiledView<'grid, T> {
+    grid: &'grid Grid<T>,
+    n: usize,
+}
+
+impl<'grid, T> Clone for TiledView<'grid, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<'grid, T> Copy for TiledView<'grid, T> {}
+
+impl<'grid, T> TiledView<'grid, T> {
+    pub fn bounds(&self) -> AABB {
+        let (rows, cols) = self.grid.size();
+        let n = self.n as i64;
+        AABB::from_range(
+            Coord::new(-n * rows, -n * cols),
+            Coord::new((n + 1) * rows, (n + 1) * cols),
+        )
+    }
+    pub fn size(&self) -> (i64, i64) {
+        let (rows, cols) = self.grid.size();
+        let tiles = 2 * self.n as i64 + 1;
+        (rows * tiles, cols * tiles)
+    }
+    pub fn contains(&self, c: Coord) -> bool {
+        let bounds = self.bounds();
+        (bounds.start().row..bounds.end().row).contains(&c.row)
+            && (bounds.start().col..bounds.end().col).contains(&c.col)
+    }
+    pub fn get(&self, c: Coord) -> Option<&'grid T> {
+        if !self.contains(c) {
+            return None;
+        }
+        self.grid.get_wrapping(c)
+    }
+    /// Which copy `c` is in, `(0, 0)` being the original, `None` for an empty grid
+    pub fn tile(&self, c: Coord) -> Option<Coord> {
+        let (rows, cols) = self.grid.size();
+        if rows == 0 || cols == 0 {
+            return None;
+        }
+        Some(Coord::new(c.row.div_euclid(rows), c.col.div_euclid(cols)))
+    }
+
+    pub fn coords(&self) -> Coords {
+        self.bounds().coords()
+    }
+    pub fn neighbors4(&self, c: Coord) -> impl Iterator<Item = (Coord, &'grid T)> {
+        let view = *self;
+        c.neighbors4()
+            .into_iter()
+            .filter_map(move |n| Some((n, view.get(n)?)))
+    }
+    pub fn neighbors8(&self, c: Coord) -> impl Iterator<Item = (Coord, &'grid T)> {
+        let view = *self;
+        c.neighbors8()
+            .into_iter()
+            .filter_map(move |n| Some((n, view.get(n)?)))
+    }
+
+    pub fn render(&self, mut f: impl FnMut(Coord, &T) -> char) -> String {
+        render::render(self.bounds(), |c| f(c, &self[c]), None)
+    }
+    /// A dense copy, the top left tile ends up at the origin
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let (rows, cols) = self.size();
+        let start = self.bounds().start();
+        Grid::from_fn(rows as usize, cols as usize, |c| self[c + start].clone())
+    }
+}
+
+impl<'grid, T> Index<Coord> for TiledView<'grid, T> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, index: Coord) -> &Self::Output {
+        self.get(index).expect("Index out of Bounds")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapping() {
+        let mut grid = Grid::parse_chars("ab\ncd\nef");
+        assert_eq!(grid.get_wrapping(Coord::new(3, 2)), Some(&'a'));
+        assert_eq!(grid.get_wrapping(Coord::new(-1, -1)), Some(&'f'));
+        assert_eq!(grid.wrap(Coord::new(-7, 5)), Some(Coord::new(2, 1)));
+        *grid.get_wrapping_mut(Coord::new(-3, 2)).unwrap() = 'x';
+        assert_eq!(grid[Coord::ORIGIN], 'x');
+
+        let n: Vec<_> = grid.neighbors4_wrapping(Coord::ORIGIN).collect();
+        assert_eq!(
+            n,
+            [
+                (Coord::new(-1, 0), &'e'),
+                (Coord::new(0, 1), &'b'),
+                (Coord::new(1, 0), &'c'),
+                (Coord::new(0, -1), &'b'),
+            ]
+        );
+        assert_eq!(grid.neighbors8_wrapping(Coord::ORIGIN).count(), 8);
+
+        let empty: Grid<char> = Grid::default();
+        assert_eq!(empty.get_wrapping(Coord::ORIGIN), None);
+    }
+
+    #[test]
+    fn tiled() {
+        let grid = Grid::parse_chars("ab\ncd");
+        let view = grid.tiled(1);
+        assert_eq!(view.size(), (6, 6));
+        assert_eq!(
+            view.bounds(),
+            AABB::from_range(Coord::new(-2, -2), Coord::new(4, 4))
+        );
+        assert_eq!(view[Coord::new(-1, -2)], 'c');
+        assert_eq!(view.get(Coord::new(4, 0)), None);
+        assert_eq!(view.tile(Coord::new(-1, 3)), Some(Coord::new(-1, 1)));
+        assert_eq!(view.neighbors4(Coord::new(-2, -2)).count(), 2);
+        assert_eq!(view.neighbors8(Coord::new(0, 0)).count(), 8);
+        assert_eq!(view.coords().count(), 36);
+
+        let rendered = view.render(|_, &c| c);
+        assert_eq!(rendered.lines().count(), 6);
+        assert!(rendered.lines().step_by(2).all(|l| l == "ababab"));
+        assert_eq!(view.to_grid().to_string(), rendered);
+    }
+
+    #[test]
+    fn empty() {
+        let grid: Grid<char> = Grid::default();
+        assert_eq!(grid.neighbors4_wrapping(Coord::ORIGIN).count(), 0);
+        assert_eq!(grid.neighbors8_wrapping(Coord::ORIGIN).count(), 0);
+
+        let view = grid.tiled(2);
+        assert_eq!(view.size(), (0, 0));
+        assert_eq!(view.tile(Coord::new(3, -1)), None);
+        assert_eq!(view.get(Coord::ORIGIN), None);
+        assert_eq!(view.neighbors8(Coord::ORIGIN).count(), 0);
+        assert_eq!(view.coords().count(), 0);
+        assert_eq!(view.to_grid().size(), (0, 0));
+    }
+}
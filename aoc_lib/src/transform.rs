@@ -0,0 +1,601 @@
+use std::{
+    fmt::Debug,
+    ops::{Bound, Index, Range, RangeBounds},
+};
+
+use crate::grid::{Coord, Coords, Grid, Sequence, AABB};
+
+// Maps a coord of the transformed grid back to the original: swap row and col when `transpose`
+// is set, then mirror whichever axes are flipped. These cover all 8 rotations and reflections.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Default)]
+struct Orientation {
+    transpose: bool,
+    flip_rows: bool,
+    flip_cols: bool,
+}
+
+impl Orientation {
+    fn transpose(self) -> Self {
+        Self {
+            transpose: !self.transpose,
+            ..self
+        }
+    }
+    fn flip_v(mut self) -> Self {
+        if self.transpose {
+            self.flip_cols = !self.flip_cols;
+        } else {
+            self.flip_rows = !self.flip_rows;
+        }
+        self
+    }
+    fn flip_h(mut self) -> Self {
+        if self.transpose {
+            self.flip_rows = !self.flip_rows;
+        } else {
+            self.flip_cols = !self.flip_cols;
+        }
+        self
+    }
+    fn rotate_cw(self) -> Self {
+        self.flip_v().transpose()
+    }
+    fn rotate_ccw(self) -> Self {
+        self.transpose().flip_v()
+    }
+    fn rotate_180(self) -> Self {
+        self.flip_v().flip_h()
+    }
+
+    // Size of the transformed grid
+    fn size(self, rows: i64, cols: i64) -> (i64, i64) {
+        if self.transpose {
+            (cols, rows)
+        } else {
+            (rows, cols)
+        }
+    }
+    // `c` must be inside the transformed grid
+    fn source(self, c: Coord, rows: i64, cols: i64) -> Coord {
+        let mut c = if self.transpose {
+            Coord::new(c.col, c.row)
+        } else {
+            c
+        };
+        if self.flip_rows {
+            c.row = rows - 1 - c.row;
+        }
+        if self.flip_cols {
+            c.col = cols - 1 - c.col;
+        }
+        c
+    }
+}
+
+/// A rotated or mirrored grid that reads through to the original without copying
+pub struct Transformed<'grid, T> {
+    grid: &'grid Grid<T>,
+    orientation: Orientation,
+}
+
+impl<'grid, T> Clone for Transformed<'grid, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<'grid, T> Copy for Transformed<'grid, T> {}
+
+impl<'grid, T> Transformed<'grid, T> {
+    fn new(grid: &'grid Grid<T>, orientation: Orientation) -> Self {
+        Self { grid, orientation }
+    }
+    fn map(self, f: impl FnOnce(Orientation) -> Orientation) -> Self {
+        Self::new(self.grid, f(self.orientation))
+    }
+
+    pub fn size(&self) -> (i64, i64) {
+        self.orientation
+            .size(self.grid.num_rows(), self.grid.num_cols())
+    }
+    pub fn num_rows(&self) -> i64 {
+        self.size().0
+    }
+    pub fn num_cols(&self) -> i64 {
+        self.size().1
+    }
+    pub fn contains(&self, c: Coord) -> bool {
+        let (rows, cols) = self.size();
+        (0..rows).contains(&c.row) && (0..cols).contains(&c.col)
+    }
+    /// The coord in the original grid that `c` reads from
+    pub fn source(&self, c: Coord) -> Option<Coord> {
+        if !self.contains(c) {
+            return None;
+        }
+        let (rows, cols) = self.grid.size();
+        Some(self.orientation.source(c, rows, cols))
+    }
+    pub fn get(&self, c: Coord) -> Option<&'grid T> {
+        self.grid.get(self.source(c)?)
+    }
+
+    #[track_caller]
+    pub fn row(&self, index: i64) -> TransformedRow<'grid, T> {
+        assert!(
+            (0..self.num_rows()).contains(&index),
+            "Row {index} out of bounds"
+        );
+        TransformedRow {
+            view: *self,
+            line: index,
+            start: 0,
+            end: self.num_cols(),
+        }
+    }
+    #[track_caller]
+    pub fn col(&self, index: i64) -> TransformedColumn<'grid, T> {
+        assert!(
+            (0..self.num_cols()).contains(&index),
+            "Column {index} out of bounds"
+        );
+        TransformedColumn {
+            view: *self,
+            line: index,
+            start: 0,
+            end: self.num_rows(),
+        }
+    }
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = TransformedRow<'grid, T>> + 'grid {
+        let view = *self;
+        (0..self.num_rows()).map(move |r| view.row(r))
+    }
+    pub fn cols(&self) -> impl DoubleEndedIterator<Item = TransformedColumn<'grid, T>> + 'grid {
+        let view = *self;
+        (0..self.num_cols()).map(move |c| view.col(c))
+    }
+    pub fn coords(&self) -> Coords {
+        let (rows, cols) = self.size();
+        AABB::from_range(Coord::ORIGIN, Coord::new(rows, cols)).coords()
+    }
+
+    pub fn transposed(self) -> Self {
+        self.map(Orientation::transpose)
+    }
+    pub fn rotated_cw(self) -> Self {
+        self.map(Orientation::rotate_cw)
+    }
+    pub fn rotated_ccw(self) -> Self {
+        self.map(Orientation::rotate_ccw)
+    }
+    pub fn rotated_180(self) -> Self {
+        self.map(Orientation::rotate_180)
+    }
+    pub fn flipped_h(self) -> Self {
+        self.map(Orientation::flip_h)
+    }
+    pub fn flipped_v(self) -> Self {
+        self.map(Orientation::flip_v)
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let (rows, cols) = self.size();
+        Grid::from_fn(rows as usize, cols as usize, |c| self[c].clone())
+    }
+}
+
+impl<'grid, T> Index<Coord> for Transformed<'grid, T> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, index: Coord) -> &Self::Output {
+        self.get(index).expect("Index out of Bounds")
+    }
+}
+
+impl<'grid, T: Debug> Debug for Transformed<'grid, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.rows()).finish()
+    }
+}
+
+impl<'a, 'b, T: PartialEq> PartialEq<Transformed<'b, T>> for Transformed<'a, T> {
+    fn eq(&self, other: &Transformed<'b, T>) -> bool {
+        self.size() == other.size() && self.coords().all(|c| self[c] == other[c])
+    }
+}
+impl<'grid, T: PartialEq> PartialEq<Grid<T>> for Transformed<'grid, T> {
+    fn eq(&self, other: &Grid<T>) -> bool {
+        *self == other.transformed()
+    }
+}
+
+/// The cells of a `TransformedRow` or `TransformedColumn`, stepping through the view
+pub struct TransformedIter<'grid, T> {
+    view: Transformed<'grid, T>,
+    first: Coord,
+    step: Coord,
+    start: i64,
+    end: i64,
+}
+
+impl<'grid, T> Iterator for TransformedIter<'grid, T> {
+    type Item = &'grid T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+        self.start += 1;
+        self.view.get(self.first + self.step * (self.start - 1))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.end - self.start) as usize;
+        (len, Some(len))
+    }
+}
+impl<'grid, T> DoubleEndedIterator for TransformedIter<'grid, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        self.view.get(self.first + self.step * self.end)
+    }
+}
+impl<'grid, T> ExactSizeIterator for TransformedIter<'grid, T> {}
+
+// `TransformedRow` and `TransformedColumn` mirror `Row` and `Column`, with `line` being the index
+// of the row or column in the view and `start..end` the slice of it
+macro_rules! transformed_line {
+    ($(#[$doc:meta])* $name:ident, $step:expr, |$line:ident, $index:ident| $coord:expr) => {
+        $(#[$doc])*
+        pub struct $name<'grid, T> {
+            view: Transformed<'grid, T>,
+            line: i64,
+            start: i64,
+            end: i64,
+        }
+
+        impl<'grid, T> $name<'grid, T> {
+            pub fn iter(&self) -> TransformedIter<'grid, T> {
+                TransformedIter {
+                    view: self.view,
+                    first: self.coord_of(0),
+                    step: $step,
+                    start: 0,
+                    end: self.end - self.start,
+                }
+            }
+            /// The coord in the view of cell `index`
+            pub const fn coord_of(&self, index: i64) -> Coord {
+                let ($line, $index) = (self.line, index + self.start);
+                $coord
+            }
+            pub fn get(&self, index: i64) -> Option<&'grid T> {
+                if !(0..self.end - self.start).contains(&index) {
+                    return None;
+                }
+                self.view.get(self.coord_of(index))
+            }
+            #[track_caller]
+            pub fn slice<I: RangeBounds<i64>>(&self, range: I) -> Self {
+                let start = match range.start_bound() {
+                    Bound::Included(&i) => self.start + i,
+                    Bound::Excluded(&i) => self.start + i + 1,
+                    Bound::Unbounded => self.start,
+                };
+                let end = match range.end_bound() {
+                    Bound::Included(&i) => self.start + i + 1,
+                    Bound::Excluded(&i) => self.start + i,
+                    Bound::Unbounded => self.end,
+                };
+                assert!(self.start <= start);
+                assert!(end <= self.end);
+                Self { start, end, ..*self }
+            }
+            /// The coords in the view, in order
+            pub fn coords(&self) -> impl DoubleEndedIterator<Item = Coord> {
+                let $line = self.line;
+                (self.start..self.end).map(move |$index| $coord)
+            }
+        }
+
+        impl<'grid, T> Index<i64> for $name<'grid, T> {
+            type Output = T;
+
+            #[track_caller]
+            fn index(&self, index: i64) -> &Self::Output {
+                self.get(index).unwrap()
+            }
+        }
+        impl<'grid, T> Clone for $name<'grid, T> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+        impl<'grid, T> Copy for $name<'grid, T> {}
+        impl<'grid, T> IntoIterator for $name<'grid, T> {
+            type Item = &'grid T;
+
+            type IntoIter = TransformedIter<'grid, T>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+        impl<'grid, T> IntoIterator for &$name<'grid, T> {
+            type Item = &'grid T;
+
+            type IntoIter = TransformedIter<'grid, T>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+        impl<'grid, T> Sequence for $name<'grid, T> {
+            type Index = i64;
+
+            type Value = T;
+
+            type Slice<'a>
+                = Self
+            where
+                Self: 'a;
+
+            fn get(&self, index: Self::Index) -> Option<&Self::Value> {
+                self.get(index)
+            }
+            fn bounds(&self) -> Range<Self::Index> {
+                0..(self.end - self.start)
+            }
+            fn slice<I: RangeBounds<Self::Index>>(&self, range: I) -> Self {
+                self.slice(range)
+            }
+        }
+        impl<'grid, T: Debug> Debug for $name<'grid, T> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_list().entries(self).finish()
+            }
+        }
+    };
+}
+
+transformed_line!(
+    /// A row of a `Transformed` view, with the same API as `Row`
+    TransformedRow,
+    Coord::E,
+    |row, col| Coord::new(row, col)
+);
+transformed_line!(
+    /// A column of a `Transformed` view, with the same API as `Column`
+    TransformedColumn,
+    Coord::S,
+    |col, row| Coord::new(row, col)
+);
+
+/// Zero-copy views. Flipping horizontally mirrors left to right, flipping vertically turns the
+/// grid upside down
+impl<T> Grid<T> {
+    /// The grid as it is, to start a chain of transforms from
+    pub fn transformed(&self) -> Transformed<'_, T> {
+        Transformed::new(self, Orientation::default())
+    }
+    pub fn transposed(&self) -> Transformed<'_, T> {
+        self.transformed().transposed()
+    }
+    pub fn rotated_cw(&self) -> Transformed<'_, T> {
+        self.transformed().rotated_cw()
+    }
+    pub fn rotated_ccw(&self) -> Transformed<'_, T> {
+        self.transformed().rotated_ccw()
+    }
+    pub fn rotated_180(&self) -> Transformed<'_, T> {
+        self.transformed().rotated_180()
+    }
+    pub fn flipped_h(&self) -> Transformed<'_, T> {
+        self.transformed().flipped_h()
+    }
+    pub fn flipped_v(&self) -> Transformed<'_, T> {
+        self.transformed().flipped_v()
+    }
+    /// Every rotation followed by every rotation of the mirror image
+    pub fn orientations(&self) -> [Transformed<'_, T>; 8] {
+        let r = self.transformed();
+        let f = r.flipped_h();
+        [
+            r,
+            r.rotated_cw(),
+            r.rotated_180(),
+            r.rotated_ccw(),
+            f,
+            f.rotated_cw(),
+            f.rotated_180(),
+            f.rotated_ccw(),
+        ]
+    }
+}
+
+/// In place versions of the views. Flips and `rotate_180` never allocate, nor do transposing and
+/// quarter turns of a square grid. Those change the shape of any other grid, so they move the
+/// cells into a new buffer.
+impl<T> Grid<T> {
+    pub fn transpose(&mut self) {
+        if self.is_square() {
+            self.transpose_square();
+        } else {
+            self.reorient(Orientation::default().transpose())
+        }
+    }
+    pub fn rotate_cw(&mut self) {
+        if self.is_square() {
+            self.transpose_square();
+            self.flip_h();
+        } else {
+            self.reorient(Orientation::default().rotate_cw())
+        }
+    }
+    pub fn rotate_ccw(&mut self) {
+        if self.is_square() {
+            self.transpose_square();
+            self.flip_v();
+        } else {
+            self.reorient(Orientation::default().rotate_ccw())
+        }
+    }
+    pub fn rotate_180(&mut self) {
+        // Reading row-major backwards
+        self.as_mut_slice().reverse();
+    }
+    pub fn flip_h(&mut self) {
+        let cols = self.num_cols() as usize;
+        if cols > 0 {
+            for row in self.as_mut_slice().chunks_exact_mut(cols) {
+                row.reverse();
+            }
+        }
+    }
+    pub fn flip_v(&mut self) {
+        let (rows, cols) = (self.num_rows() as usize, self.num_cols() as usize);
+        let data = self.as_mut_slice();
+        for r in 0..rows / 2 {
+            let (top, bottom) = data.split_at_mut((rows - 1 - r) * cols);
+            top[r * cols..(r + 1) * cols].swap_with_slice(&mut bottom[..cols]);
+        }
+    }
+
+    fn is_square(&self) -> bool {
+        self.num_rows() == self.num_cols()
+    }
+    fn transpose_square(&mut self) {
+        let n = self.num_rows() as usize;
+        let data = self.as_mut_slice();
+        for r in 0..n {
+            for c in r + 1..n {
+                data.swap(r * n + c, c * n + r);
+            }
+        }
+    }
+
+    fn reorient(&mut self, orientation: Orientation) {
+        let (rows, cols) = self.size();
+        let (new_rows, new_cols) = orientation.size(rows, cols);
+
+        let mut old: Vec<_> = std::mem::take(self)
+            .into_vec()
+            .into_iter()
+            .map(Some)
+            .collect();
+        *self = Grid::from_fn(new_rows as usize, new_cols as usize, |c| {
+            let src = orientation.source(c, rows, cols);
+            old[(src.row * cols + src.col) as usize].take().unwrap()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u8> {
+        Grid::parse_digits("123\n456")
+    }
+
+    #[test]
+    fn views() {
+        let g = grid();
+        let rows = |v: Transformed<u8>| -> Vec<Vec<u8>> {
+            v.rows().map(|r| r.iter().copied().collect()).collect()
+        };
+
+        assert_eq!(rows(g.transformed()), [[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(rows(g.transposed()), [[1, 4], [2, 5], [3, 6]]);
+        assert_eq!(rows(g.rotated_cw()), [[4, 1], [5, 2], [6, 3]]);
+        assert_eq!(rows(g.rotated_ccw()), [[3, 6], [2, 5], [1, 4]]);
+        assert_eq!(rows(g.rotated_180()), [[6, 5, 4], [3, 2, 1]]);
+        assert_eq!(rows(g.flipped_h()), [[3, 2, 1], [6, 5, 4]]);
+        assert_eq!(rows(g.flipped_v()), [[4, 5, 6], [1, 2, 3]]);
+
+        // Views compose
+        assert_eq!(rows(g.rotated_cw().rotated_cw()), rows(g.rotated_180()));
+        assert_eq!(rows(g.rotated_cw().rotated_ccw()), rows(g.transformed()));
+        assert_eq!(rows(g.flipped_h().transposed()), rows(g.rotated_ccw()));
+
+        let v = g.rotated_cw();
+        assert_eq!(v.size(), (3, 2));
+        assert_eq!(v[Coord::new(0, 0)], 4);
+        assert_eq!(v.get(Coord::new(0, 2)), None);
+        assert_eq!(v.source(Coord::new(2, 1)), Some(Coord::new(0, 2)));
+        let col: Vec<_> = v.col(1).iter().copied().collect();
+        assert_eq!(col, [1, 2, 3]);
+        assert_eq!(v.col(1).iter().rev().len(), 3);
+        assert_eq!(v.cols().count(), 2);
+        assert_eq!(v.coords().count(), 6);
+        assert_eq!(v.to_grid(), Grid::from([[4, 1], [5, 2], [6, 3]]));
+
+        assert_eq!(v, Grid::from([[4, 1], [5, 2], [6, 3]]));
+        assert_ne!(v, g);
+
+        let all: Vec<_> = g.orientations().map(|v| v.to_grid()).into();
+        for (i, a) in all.iter().enumerate() {
+            assert!(all[i + 1..].iter().all(|b| a != b));
+        }
+    }
+
+    #[test]
+    fn lines() {
+        let g = grid();
+        let t = g.transposed();
+        // Rows of the view compare with rows and columns of grids
+        assert_eq!(t.row(1), g.col(1));
+        assert_eq!(t.col(0), g.row(0));
+        assert_eq!(g.rotated_180().row(0), [6, 5, 4]);
+        assert_ne!(t.row(0), g.row(0));
+
+        let r = g.rotated_cw().col(0);
+        assert_eq!(r[2], 6);
+        assert_eq!(r.get(3), None);
+        assert_eq!(r.slice(1..), [5, 6]);
+        assert_eq!(r.slice(1..).get(1), Some(&6));
+        assert_eq!(r.slice(..=1).get(2), None);
+        assert_eq!(Sequence::bounds(&r.slice(1..2)), 0..1);
+        assert_eq!(
+            r.coords().collect::<Vec<_>>(),
+            [Coord::new(0, 0), Coord::new(1, 0), Coord::new(2, 0)]
+        );
+        assert_eq!(r.slice(1..).coord_of(0), Coord::new(1, 0));
+        assert_eq!(format!("{:?}", g.flipped_h().row(1)), "[6, 5, 4]");
+    }
+
+    #[test]
+    #[should_panic(expected = "Row 3 out of bounds")]
+    fn line_out_of_bounds() {
+        grid().transposed().row(3);
+    }
+
+    #[test]
+    fn in_place() {
+        let check = |g: &Grid<u8>, f: fn(&mut Grid<u8>), view: fn(&Grid<u8>) -> Transformed<u8>| {
+            let mut moved = g.clone();
+            f(&mut moved);
+            assert_eq!(view(g), moved);
+        };
+        let square = Grid::parse_digits("123\n456\n789");
+        for g in [grid(), square, Grid::default(), Grid::filled(0, 3, 0)] {
+            check(&g, Grid::transpose, Grid::transposed);
+            check(&g, Grid::rotate_cw, Grid::rotated_cw);
+            check(&g, Grid::rotate_ccw, Grid::rotated_ccw);
+            check(&g, Grid::rotate_180, Grid::rotated_180);
+            check(&g, Grid::flip_h, Grid::flipped_h);
+            check(&g, Grid::flip_v, Grid::flipped_v);
+        }
+
+        // No Clone needed
+        let mut g = Grid::from([[String::from("a"), String::from("b")]]);
+        g.rotate_cw();
+        assert_eq!(g.size(), (2, 1));
+        assert_eq!(g[Coord::new(1, 0)], "b");
+    }
+}
@@ -0,0 +1,354 @@
+use std::{
+    fmt::Debug,
+    ops::{Index, IndexMut},
+};
+
+use crate::grid::{Column, ColumnMut, Coord, Coords, Grid, Row, RowMut, AABB};
+
+// The corners of `area`, which has to fit in a grid of `size`
+#[track_caller]
+fn check(area: AABB, size: (i64, i64)) -> (Coord, Coord) {
+    let (start, end) = (area.start(), area.end());
+    assert!(
+        0 <= start.row && start.row <= end.row && end.row <= size.0,
+        "View rows {}..{} out of bounds",
+        start.row,
+        end.row
+    );
+    assert!(
+        0 <= start.col && start.col <= end.col && end.col <= size.1,
+        "View columns {}..{} out of bounds",
+        start.col,
+        end.col
+    );
+    (start, end)
+}
+
+/// A rectangle of a grid, addressed from its own top left corner
+pub struct GridView<'grid, T> {
+    grid: &'grid Grid<T>,
+    start: Coord,
+    end: Coord,
+}
+
+impl<'grid, T> Clone for GridView<'grid, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<'grid, T> Copy for GridView<'grid, T> {}
+
+impl<'grid, T> GridView<'grid, T> {
+    /// Where the view's origin is in the grid
+    pub fn start(&self) -> Coord {
+        self.start
+    }
+    pub fn size(&self) -> (i64, i64) {
+        let size = self.end - self.start;
+        (size.row, size.col)
+    }
+    pub fn num_rows(&self) -> i64 {
+        self.size().0
+    }
+    pub fn num_cols(&self) -> i64 {
+        self.size().1
+    }
+    pub fn contains(&self, c: Coord) -> bool {
+        let (rows, cols) = self.size();
+        (0..rows).contains(&c.row) && (0..cols).contains(&c.col)
+    }
+    pub fn get(&self, c: Coord) -> Option<&'grid T> {
+        if !self.contains(c) {
+            return None;
+        }
+        self.grid.get(c + self.start)
+    }
+
+    pub fn row(&self, index: i64) -> Row<'grid, T> {
+        assert!(
+            (0..self.num_rows()).contains(&index),
+            "Row {index} out of bounds"
+        );
+        self.grid
+            .row(self.start.row + index)
+            .slice(self.start.col..self.end.col)
+    }
+    pub fn col(&self, index: i64) -> Column<'grid, T> {
+        assert!(
+            (0..self.num_cols()).contains(&index),
+            "Column {index} out of bounds"
+        );
+        self.grid
+            .col(self.start.col + index)
+            .slice(self.start.row..self.end.row)
+    }
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = Row<'grid, T>> + 'grid {
+        let view = *self;
+        (0..self.num_rows()).map(move |r| view.row(r))
+    }
+    pub fn cols(&self) -> impl DoubleEndedIterator<Item = Column<'grid, T>> + 'grid {
+        let view = *self;
+        (0..self.num_cols()).map(move |c| view.col(c))
+    }
+    pub fn coords(&self) -> Coords {
+        let (rows, cols) = self.size();
+        AABB::from_range(Coord::ORIGIN, Coord::new(rows, cols)).coords()
+    }
+
+    /// A view of part of this view, `area` is relative to this view
+    #[track_caller]
+    pub fn view(&self, area: AABB) -> GridView<'grid, T> {
+        let (start, end) = check(area, self.size());
+        GridView {
+            grid: self.grid,
+            start: self.start + start,
+            end: self.start + end,
+        }
+    }
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let (rows, cols) = self.size();
+        Grid::from_fn(rows as usize, cols as usize, |c| self[c].clone())
+    }
+}
+
+impl<'grid, T> Index<Coord> for GridView<'grid, T> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, index: Coord) -> &Self::Output {
+        self.get(index).expect("Index out of Bounds")
+    }
+}
+
+impl<'grid, T: Debug> Debug for GridView<'grid, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.rows()).finish()
+    }
+}
+
+impl<'a, 'b, T: PartialEq> PartialEq<GridView<'b, T>> for GridView<'a, T> {
+    fn eq(&self, other: &GridView<'b, T>) -> bool {
+        self.size() == other.size() && self.coords().all(|c| self[c] == other[c])
+    }
+}
+impl<'grid, T: PartialEq> PartialEq<Grid<T>> for GridView<'grid, T> {
+    fn eq(&self, other: &Grid<T>) -> bool {
+        *self == other.view(other.bounds())
+    }
+}
+
+/// A mutable rectangle of a grid, addressed from its own top left corner
+pub struct GridViewMut<'grid, T> {
+    grid: &'grid mut Grid<T>,
+    start: Coord,
+    end: Coord,
+}
+
+impl<'grid, T> GridViewMut<'grid, T> {
+    pub fn as_view(&self) -> GridView<'_, T> {
+        GridView {
+            grid: self.grid,
+            start: self.start,
+            end: self.end,
+        }
+    }
+    pub fn start(&self) -> Coord {
+        self.start
+    }
+    pub fn size(&self) -> (i64, i64) {
+        self.as_view().size()
+    }
+    pub fn contains(&self, c: Coord) -> bool {
+        self.as_view().contains(c)
+    }
+    pub fn get(&self, c: Coord) -> Option<&T> {
+        self.as_view().get(c)
+    }
+    pub fn get_mut(&mut self, c: Coord) -> Option<&mut T> {
+        if !self.contains(c) {
+            return None;
+        }
+        self.grid.get_mut(c + self.start)
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = Row<'_, T>> {
+        self.as_view().rows()
+    }
+    pub fn cols(&self) -> impl DoubleEndedIterator<Item = Column<'_, T>> {
+        self.as_view().cols()
+    }
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = RowMut<'_, T>> {
+        let (start, end) = (self.start, self.end);
+        self.grid
+            .rows_mut()
+            .into_iter()
+            .skip(start.row as usize)
+            .take((end.row - start.row) as usize)
+            .map(move |r| r.slice_mut(start.col..end.col))
+    }
+    pub fn cols_mut(&mut self) -> impl Iterator<Item = ColumnMut<'_, T>> {
+        let (start, end) = (self.start, self.end);
+        self.grid
+            .cols_mut()
+            .into_iter()
+            .skip(start.col as usize)
+            .take((end.col - start.col) as usize)
+            .map(move |c| c.slice_mut(start.row..end.row))
+    }
+    pub fn coords(&self) -> Coords {
+        self.as_view().coords()
+    }
+
+    pub fn fill(&mut self, v: T)
+    where
+        T: Clone,
+    {
+        for c in self.coords() {
+            self[c] = v.clone();
+        }
+    }
+}
+
+impl<'grid, T> Index<Coord> for GridViewMut<'grid, T> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, index: Coord) -> &Self::Output {
+        self.get(index).expect("Index out of Bounds")
+    }
+}
+impl<'grid, T> IndexMut<Coord> for GridViewMut<'grid, T> {
+    #[track_caller]
+    fn index_mut(&mut self, index: Coord) -> &mut Self::Output {
+        self.get_mut(index).expect("Index out of Bounds")
+    }
+}
+
+impl<T> Grid<T> {
+    /// The cells of `area`, which must lie inside the grid
+    #[track_caller]
+    pub fn view(&self, area: AABB) -> GridView<'_, T> {
+        let (start, end) = check(area, self.size());
+        GridView {
+            grid: self,
+            start,
+            end,
+        }
+    }
+    #[track_caller]
+    pub fn view_mut(&mut self, area: AABB) -> GridViewMut<'_, T> {
+        let (start, end) = check(area, self.size());
+        GridViewMut {
+            grid: self,
+            start,
+            end,
+        }
+    }
+
+    /// Every `rows` by `cols` patch of the grid in row-major order of their top left corners
+    pub fn windows(&self, rows: i64, cols: i64) -> impl Iterator<Item = GridView<'_, T>> {
+        let (grid_rows, grid_cols) = self.size();
+        let corners = AABB::from_range(
+            Coord::ORIGIN,
+            Coord::new(grid_rows - rows + 1, grid_cols - cols + 1),
+        );
+        corners
+            .coords()
+            .map(move |c| self.view(AABB::from_range(c, c + Coord::new(rows, cols))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u8> {
+        Grid::parse_digits(
+            "
+            1234
+            5678
+            9012
+            ",
+        )
+    }
+
+    fn area(r0: i64, c0: i64, r1: i64, c1: i64) -> AABB {
+        AABB::from_range(Coord::new(r0, c0), Coord::new(r1, c1))
+    }
+
+    #[test]
+    fn view() {
+        let grid = grid();
+        let view = grid.view(area(1, 1, 3, 4));
+        assert_eq!(view.size(), (2, 3));
+        assert_eq!(view[Coord::ORIGIN], 6);
+        assert_eq!(view.get(Coord::new(0, 3)), None);
+        assert_eq!(view.get(Coord::new(-1, 0)), None);
+
+        let rows: Vec<Vec<u8>> = view.rows().map(|r| r.iter().copied().collect()).collect();
+        assert_eq!(rows, [[6, 7, 8], [0, 1, 2]]);
+        let narrow = grid.view(area(0, 1, 2, 3));
+        let rows: Vec<Vec<u8>> = narrow.rows().map(|r| r.iter().copied().collect()).collect();
+        assert_eq!(rows, [[2, 3], [6, 7]]);
+        let col: Vec<u8> = view.col(2).iter().copied().collect();
+        assert_eq!(col, [8, 2]);
+        assert_eq!(view.cols().count(), 3);
+
+        let inner = view.view(area(1, 1, 2, 3));
+        assert_eq!(inner.start(), Coord::new(2, 2));
+        assert_eq!(inner, Grid::from([[1, 2]]));
+        assert_eq!(view.to_grid(), Grid::from([[6, 7, 8], [0, 1, 2]]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        grid().view(area(0, 0, 4, 1));
+    }
+
+    #[test]
+    fn view_mut() {
+        let mut grid = grid();
+        let mut view = grid.view_mut(area(0, 2, 3, 4));
+        view[Coord::new(1, 1)] = 0;
+        for mut r in view.rows_mut() {
+            *r.get_mut(0).unwrap() += 1;
+        }
+        for mut c in view.cols_mut() {
+            assert_eq!(c.iter_mut().count(), 3);
+        }
+        assert_eq!(view.get(Coord::new(0, 2)), None);
+        assert_eq!(grid, Grid::from([[1, 2, 4, 4], [5, 6, 8, 0], [9, 0, 2, 2]]));
+
+        grid.view_mut(area(0, 0, 2, 2)).fill(0);
+        assert_eq!(
+            grid.row(1).iter().copied().collect::<Vec<_>>(),
+            [0, 0, 8, 0]
+        );
+    }
+
+    #[test]
+    fn windows() {
+        let grid = grid();
+        assert_eq!(grid.windows(2, 2).count(), 6);
+        assert_eq!(grid.windows(3, 4).count(), 1);
+        assert_eq!(grid.windows(4, 1).count(), 0);
+
+        let sums: Vec<u32> = grid
+            .windows(2, 3)
+            .map(|w| w.coords().map(|c| w[c] as u32).sum())
+            .collect();
+        assert_eq!(sums, [24, 30, 28, 24]);
+
+        let pattern = Grid::from([[7, 8], [1, 2]]);
+        let found: Vec<_> = grid
+            .windows(2, 2)
+            .filter(|w| *w == pattern)
+            .map(|w| w.start())
+            .collect();
+        assert_eq!(found, [Coord::new(1, 2)]);
+    }
+}
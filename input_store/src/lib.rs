@@ -0,0 +1,236 @@
+use std::fmt::{Debug, Display};
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+
+pub use paths::{Paths, DIR_VAR, PROFILE_VAR};
+
+mod paths;
+
+pub const STORE_DIR: &str = "inputs.enc";
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+
+const KEYRING_SERVICE: &str = "aoc_runner_input_key";
+
+// Layout of an encrypted input: VERSION | nonce | ciphertext
+const VERSION: u8 = 1;
+const NONCE_LEN: usize = 24;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    InvalidKey,
+    Keyring(keyring::Error),
+    Io(std::io::Error),
+    Corrupt,
+    Decrypt,
+    Config(String),
+    InvalidProfile(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidKey => write!(f, "input key must be 64 hex characters"),
+            Error::Keyring(e) => write!(f, "keyring error: {e}"),
+            Error::Io(e) => write!(f, "{e}"),
+            Error::Corrupt => write!(f, "encrypted input is corrupt or from a newer version"),
+            Error::Decrypt => write!(f, "failed to decrypt input, is the key correct?"),
+            Error::Config(msg) => write!(f, "invalid config in {msg}"),
+            Error::InvalidProfile(name) => write!(
+                f,
+                "invalid profile `{name}`, use letters, digits, `-` and `_` and not only digits"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<keyring::Error> for Error {
+    fn from(value: keyring::Error) -> Self {
+        Error::Keyring(value)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(value: std::io::Error) -> Self {
+        Error::Io(value)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Key([u8; 32]);
+
+impl Key {
+    pub fn generate() -> Self {
+        Self(XChaCha20Poly1305::generate_key(&mut OsRng).into())
+    }
+
+    pub fn from_hex(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.len() != 64 || !s.is_ascii() {
+            return Err(Error::InvalidKey);
+        }
+        let mut key = [0; 32];
+        for (i, b) in key.iter_mut().enumerate() {
+            *b = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).map_err(|_| Error::InvalidKey)?;
+        }
+        Ok(Self(key))
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    // The environment takes priority over the keyring so CI can supply the key
+    pub fn load() -> Result<Option<Self>> {
+        if let Ok(hex) = std::env::var(KEY_VAR) {
+            if !hex.is_empty() {
+                return Self::from_hex(&hex).map(Some);
+            }
+        }
+
+        match keyring_entry()?.get_password() {
+            Ok(hex) => Self::from_hex(&hex).map(Some),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        keyring_entry()?.set_password(&self.to_hex())?;
+        Ok(())
+    }
+}
+
+impl Debug for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Key(..)")
+    }
+}
+
+fn keyring_entry() -> Result<keyring::Entry> {
+    Ok(keyring::Entry::new(KEYRING_SERVICE, &whoami::username())?)
+}
+
+// Binding the puzzle into the ciphertext stops files from being swapped around
+fn associated_data(year: u64, day: u64) -> String {
+    format!("aoc/{year}/day{day}")
+}
+
+pub fn encrypt(key: &Key, year: u64, day: u64, input: &str) -> Vec<u8> {
+    let cipher = XChaCha20Poly1305::new(&key.0.into());
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let aad = associated_data(year, day);
+    let payload = Payload {
+        msg: input.as_bytes(),
+        aad: aad.as_bytes(),
+    };
+    let ciphertext = cipher
+        .encrypt(&nonce, payload)
+        .expect("Encryption can't fail for in-memory buffers");
+
+    let mut out = Vec::with_capacity(1 + NONCE_LEN + ciphertext.len());
+    out.push(VERSION);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    out
+}
+
+pub fn decrypt(key: &Key, year: u64, day: u64, data: &[u8]) -> Result<String> {
+    if data.len() < 1 + NONCE_LEN || data[0] != VERSION {
+        return Err(Error::Corrupt);
+    }
+    let (nonce, ciphertext) = data[1..].split_at(NONCE_LEN);
+
+    let cipher = XChaCha20Poly1305::new(&key.0.into());
+    let aad = associated_data(year, day);
+    let payload = Payload {
+        msg: ciphertext,
+        aad: aad.as_bytes(),
+    };
+    let plain = cipher
+        .decrypt(XNonce::from_slice(nonce), payload)
+        .map_err(|_| Error::Decrypt)?;
+    String::from_utf8(plain).map_err(|_| Error::Corrupt)
+}
+
+pub fn read(paths: &Paths, key: &Key, year: u64, day: u64) -> Result<String> {
+    let data = std::fs::read(paths.encrypted(year, day))?;
+    decrypt(key, year, day, &data)
+}
+
+pub fn write(paths: &Paths, key: &Key, year: u64, day: u64, input: &str) -> Result<()> {
+    let path = paths.encrypted(year, day);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, encrypt(key, year, day, input))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "1abc2\npqr3stu8vwx\n";
+
+    #[test]
+    fn round_trip() {
+        let key = Key::generate();
+        let data = encrypt(&key, 2023, 1, INPUT);
+        assert_eq!(decrypt(&key, 2023, 1, &data).unwrap(), INPUT);
+
+        // Fresh nonce every time
+        assert_ne!(data, encrypt(&key, 2023, 1, INPUT));
+    }
+
+    #[test]
+    fn rejects_wrong_key_and_puzzle() {
+        let key = Key::generate();
+        let data = encrypt(&key, 2023, 1, INPUT);
+
+        assert!(matches!(
+            decrypt(&Key::generate(), 2023, 1, &data),
+            Err(Error::Decrypt)
+        ));
+        assert!(matches!(decrypt(&key, 2023, 2, &data), Err(Error::Decrypt)));
+        assert!(matches!(decrypt(&key, 2022, 1, &data), Err(Error::Decrypt)));
+
+        let mut tampered = data.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            decrypt(&key, 2023, 1, &tampered),
+            Err(Error::Decrypt)
+        ));
+        assert!(matches!(
+            decrypt(&key, 2023, 1, &data[..10]),
+            Err(Error::Corrupt)
+        ));
+    }
+
+    #[test]
+    fn hex() {
+        let key = Key::generate();
+        assert_eq!(Key::from_hex(&key.to_hex()).unwrap(), key);
+        assert!(Key::from_hex("abcd").is_err());
+        assert!(Key::from_hex(&"zz".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn files() {
+        let root = std::env::temp_dir().join(format!("aoc_input_store_{}", std::process::id()));
+        let paths = Paths::new(root.clone(), "input".into());
+        let key = Key::generate();
+
+        write(&paths, &key, 2022, 5, INPUT).unwrap();
+        assert!(root.join("inputs.enc/2022/day5").exists());
+        assert_eq!(read(&paths, &key, 2022, 5).unwrap(), INPUT);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
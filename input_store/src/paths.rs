@@ -0,0 +1,229 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::{Error, Result, STORE_DIR};
+
+pub const DIR_VAR: &str = "AOC_INPUT_DIR";
+pub const PROFILE_VAR: &str = "AOC_PROFILE";
+
+const DEFAULT_DIR: &str = "input";
+
+// Where inputs live, anchored at the workspace root.
+//
+// The input directory comes from `AOC_INPUT_DIR`, then `input-dir` under
+// `[workspace.metadata.aoc]` in the workspace manifest, then `{root}/input`.
+// Relative paths are taken from the workspace root.
+//
+// Each profile is a separate account with its own inputs, kept in a
+// `{profile}` subdirectory of both the input directory and the store.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Paths {
+    root: PathBuf,
+    input_dir: PathBuf,
+    profile: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct Manifest {
+    workspace: Option<Workspace>,
+}
+
+#[derive(Deserialize, Default)]
+struct Workspace {
+    #[serde(default)]
+    metadata: Metadata,
+}
+
+#[derive(Deserialize, Default)]
+struct Metadata {
+    aoc: Option<Config>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+struct Config {
+    input_dir: Option<PathBuf>,
+}
+
+impl Paths {
+    pub fn new(root: PathBuf, input_dir: PathBuf) -> Self {
+        Self {
+            input_dir: root.join(input_dir),
+            root,
+            profile: None,
+        }
+    }
+
+    // Profiles share a directory with the years, so they can't look like one
+    pub fn with_profile(mut self, profile: &str) -> Result<Self> {
+        let valid = !profile.is_empty()
+            && !profile.bytes().all(|b| b.is_ascii_digit())
+            && profile
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+        if !valid {
+            return Err(Error::InvalidProfile(profile.to_string()));
+        }
+        self.profile = Some(profile.to_string());
+        Ok(self)
+    }
+
+    pub fn discover(start: &Path) -> Result<Self> {
+        let env = std::env::var_os(DIR_VAR)
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from);
+        let paths = Self::resolve(start, env)?;
+        match std::env::var(PROFILE_VAR) {
+            Ok(profile) if !profile.is_empty() => paths.with_profile(&profile),
+            _ => Ok(paths),
+        }
+    }
+
+    fn resolve(start: &Path, env: Option<PathBuf>) -> Result<Self> {
+        let (root, config) = find_workspace(start)?;
+        let input_dir = env
+            .or(config.input_dir)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_DIR));
+
+        Ok(Self::new(root, input_dir))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+    pub fn input_dir(&self) -> PathBuf {
+        self.in_profile(&self.input_dir)
+    }
+    pub fn store_dir(&self) -> PathBuf {
+        self.in_profile(&self.root.join(STORE_DIR))
+    }
+
+    pub fn input(&self, year: u64, day: u64) -> PathBuf {
+        self.input_dir()
+            .join(year.to_string())
+            .join(format!("day{day}.txt"))
+    }
+    pub fn encrypted(&self, year: u64, day: u64) -> PathBuf {
+        self.store_dir()
+            .join(year.to_string())
+            .join(format!("day{day}"))
+    }
+
+    fn in_profile(&self, dir: &Path) -> PathBuf {
+        match &self.profile {
+            Some(profile) => dir.join(profile),
+            None => dir.to_path_buf(),
+        }
+    }
+}
+
+// Like cargo, the nearest manifest with a `[workspace]` table is the root, falling back to the
+// nearest manifest of any kind
+fn find_workspace(start: &Path) -> Result<(PathBuf, Config)> {
+    let mut nearest = None;
+
+    for dir in start.ancestors() {
+        let manifest = dir.join("Cargo.toml");
+        let Ok(text) = std::fs::read_to_string(&manifest) else {
+            continue;
+        };
+        let parsed: Manifest = toml::from_str(&text)
+            .map_err(|e| Error::Config(format!("{}: {e}", manifest.display())))?;
+
+        if let Some(workspace) = parsed.workspace {
+            let config = workspace.metadata.aoc.unwrap_or_default();
+            return Ok((dir.to_path_buf(), config));
+        }
+        nearest.get_or_insert_with(|| dir.to_path_buf());
+    }
+
+    let root = nearest.unwrap_or_else(|| start.to_path_buf());
+    Ok((root, Config::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace(name: &str, manifest: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("aoc_paths_{name}_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("member/src")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), manifest).unwrap();
+        std::fs::write(
+            root.join("member/Cargo.toml"),
+            "[package]\nname = \"member\"",
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn defaults_to_workspace_root() {
+        let root = workspace("default", "[workspace]\nmembers = [\"member\"]");
+
+        let paths = Paths::resolve(&root.join("member/src"), None).unwrap();
+        assert_eq!(paths.root(), root);
+        assert_eq!(paths.input(2022, 3), root.join("input/2022/day3.txt"));
+        assert_eq!(paths.store_dir(), root.join("inputs.enc"));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn configured() {
+        let root = workspace(
+            "config",
+            "[workspace]\nmembers = [\"member\"]\n\n[workspace.metadata.aoc]\ninput-dir = \"../shared\"",
+        );
+
+        let paths = Paths::resolve(&root.join("member"), None).unwrap();
+        assert_eq!(paths.input_dir(), root.join("../shared"));
+
+        // The environment beats the manifest, and absolute paths are kept as is
+        let paths = Paths::resolve(&root, Some("/tmp/aoc".into())).unwrap();
+        assert_eq!(paths.input(2023, 25), Path::new("/tmp/aoc/2023/day25.txt"));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn profiles() {
+        let paths = Paths::new("/aoc".into(), "input".into());
+        assert_eq!(paths.profile(), None);
+
+        let alice = paths.clone().with_profile("alice").unwrap();
+        assert_eq!(alice.profile(), Some("alice"));
+        assert_eq!(
+            alice.input(2023, 7),
+            Path::new("/aoc/input/alice/2023/day7.txt")
+        );
+        assert_eq!(
+            alice.encrypted(2023, 7),
+            Path::new("/aoc/inputs.enc/alice/2023/day7")
+        );
+        assert_eq!(
+            paths.encrypted(2023, 7),
+            Path::new("/aoc/inputs.enc/2023/day7")
+        );
+
+        for bad in ["", "2023", "../bob", "a/b", "bob smith"] {
+            assert!(matches!(
+                paths.clone().with_profile(bad),
+                Err(Error::InvalidProfile(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn this_workspace() {
+        let paths = Paths::resolve(Path::new(env!("CARGO_MANIFEST_DIR")), None).unwrap();
+        assert_eq!(
+            paths.root(),
+            Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+        );
+    }
+}
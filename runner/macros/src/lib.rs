@@ -17,8 +17,6 @@ pub fn aoc(attr: TokenStream, input: TokenStream) -> TokenStream {
 
     let i = func.sig.ident.clone();
 
-    let path = format!("/input/{year}/day{day}.txt");
-
     quote::quote! {
 
         const _: () = {
@@ -28,7 +26,7 @@ pub fn aoc(attr: TokenStream, input: TokenStream) -> TokenStream {
                 day: #day,
                 part: #part,
                 f: |i| #i(i).to_string(),
-                input: concat!(env!("CARGO_MANIFEST_DIR"), #path)
+                manifest_dir: env!("CARGO_MANIFEST_DIR"),
             };
         };
 
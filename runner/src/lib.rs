@@ -1,4 +1,13 @@
-use std::time::Instant;
+use std::{
+    any::Any,
+    fmt::{Display, Write},
+    panic,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+use clap::Parser;
 
 pub use macros::*;
 
@@ -10,7 +19,7 @@ pub struct Problem {
     pub day: u64,
     pub part: u64,
     pub f: fn(&str) -> String,
-    pub input: &'static str,
+    pub manifest_dir: &'static str,
 }
 
 #[doc(hidden)]
@@ -23,14 +32,111 @@ pub fn run_recent() {
     run_year(year);
 }
 
+/// Run Advent of Code solutions
+#[derive(Parser, Debug)]
+struct Cli {
+    /// Account whose inputs to use, beats AOC_PROFILE
+    #[arg(long)]
+    profile: Option<String>,
+    /// Run a day against every input in DIR, by default `{input dir}/{year}/day{day}/`
+    #[arg(long, num_args = 2..=3, value_names = ["YEAR", "DAY", "DIR"])]
+    validate: Option<Vec<String>>,
+}
+
+impl Cli {
+    fn validate_args(&self) -> Result<Option<(u64, u64, Option<PathBuf>)>, String> {
+        let Some(args) = &self.validate else {
+            return Ok(None);
+        };
+        let number = |name, arg: &String| {
+            arg.parse::<u64>()
+                .map_err(|e| format!("invalid {name} '{arg}' for '--validate': {e}"))
+        };
+        Ok(Some((
+            number("YEAR", &args[0])?,
+            number("DAY", &args[1])?,
+            args.get(2).map(PathBuf::from),
+        )))
+    }
+}
+
+// Parsed on first use, exiting with clap's usage message on bad arguments
+fn cli() -> &'static Cli {
+    static CLI: OnceLock<Cli> = OnceLock::new();
+    CLI.get_or_init(Cli::parse)
+}
+
+/// Handles `--validate` when it was passed, exiting with a failure status if any input failed.
+/// Returns whether it ran, so `main` can skip its usual runs
+pub fn validate_from_args() -> bool {
+    let args = cli().validate_args().unwrap_or_else(|msg| {
+        clap::Error::raw(clap::error::ErrorKind::ValueValidation, format!("{msg}\n")).exit()
+    });
+    let Some((year, day, dir)) = args else {
+        return false;
+    };
+    let result = match dir {
+        Some(dir) => validate_dir(year, day, &dir),
+        None => validate(year, day),
+    };
+    match result {
+        Ok(true) => true,
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1)
+        }
+    }
+}
+
+fn paths(p: &Problem) -> input_store::Paths {
+    let paths = input_store::Paths::discover(Path::new(p.manifest_dir));
+    let paths = match &cli().profile {
+        Some(profile) => paths.and_then(|paths| paths.with_profile(profile)),
+        None => paths,
+    };
+    paths.unwrap_or_else(|e| panic!("Failed to locate inputs: {e}"))
+}
+
+// Falls back to the encrypted store when the plain input isn't on disk
+fn load_input(p: &Problem, paths: &input_store::Paths) -> String {
+    let file = paths.input(p.year, p.day);
+    if let Ok(input) = std::fs::read_to_string(&file) {
+        return input;
+    }
+
+    let key = input_store::Key::load()
+        .unwrap_or_else(|e| panic!("Failed to load input key: {e}"))
+        .unwrap_or_else(|| {
+            panic!(
+                "No input at {} and no key to decrypt the store, set {} or run `setup key set`",
+                file.display(),
+                input_store::KEY_VAR
+            )
+        });
+    input_store::read(paths, &key, p.year, p.day).unwrap_or_else(|e| {
+        panic!(
+            "Failed to read {}: {e}",
+            paths.encrypted(p.year, p.day).display()
+        )
+    })
+}
+
 fn run_problem_p(p: &Problem) {
-    let input = std::fs::read_to_string(p.input).unwrap();
+    let paths = paths(p);
+    let input = load_input(p, &paths);
 
     let time = Instant::now();
     let result = (p.f)(&input);
     let elapsed = time.elapsed();
 
-    println!("[AOC - {} - day {} - part {}]", p.year, p.day, p.part);
+    match paths.profile() {
+        Some(profile) => println!(
+            "[AOC - {} - day {} - part {} - {profile}]",
+            p.year, p.day, p.part
+        ),
+        None => println!("[AOC - {} - day {} - part {}]", p.year, p.day, p.part),
+    }
     println!("\tresult: {result}");
     println!("\ttime: {}\n", humantime::format_duration(elapsed))
 }
@@ -62,3 +168,384 @@ pub fn run_problem(year: u64, day: u64) {
         run_problem_p(p2)
     }
 }
+
+#[derive(Debug)]
+pub enum ValidateError {
+    NoSolutions { year: u64, day: u64 },
+    NoInputs(PathBuf),
+    Io(PathBuf, std::io::Error),
+}
+
+impl Display for ValidateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidateError::NoSolutions { year, day } => {
+                write!(f, "No solutions registered for {year} day {day}")
+            }
+            ValidateError::NoInputs(dir) => write!(f, "No inputs found in {}", dir.display()),
+            ValidateError::Io(path, e) => write!(f, "Failed to read {}: {e}", path.display()),
+        }
+    }
+}
+impl std::error::Error for ValidateError {}
+
+// Runs a day against every `*.txt` in `{input dir}/{year}/day{day}/`
+pub fn validate(year: u64, day: u64) -> Result<bool, ValidateError> {
+    let p = find_problems(year, day)
+        .next()
+        .ok_or(ValidateError::NoSolutions { year, day })?;
+    let dir = paths(p)
+        .input_dir()
+        .join(year.to_string())
+        .join(format!("day{day}"));
+    validate_dir(year, day, &dir)
+}
+
+// Runs both parts against every `*.txt` in `dir`, catching panics so one bad input doesn't hide
+// the rest. `{name}.answers` next to `{name}.txt` holds the expected answer for part 1 on the
+// first line and part 2 on the second, either may be left blank. Returns whether every run
+// finished and matched its expected answer.
+pub fn validate_dir(year: u64, day: u64, dir: &Path) -> Result<bool, ValidateError> {
+    let rows = run_dir(year, day, dir)?;
+    let inputs = rows.iter().filter(|r| r.part == rows[0].part).count();
+
+    println!(
+        "[AOC - {year} - day {day} - {inputs} inputs in {}]",
+        dir.display()
+    );
+    print!("{}", render(&rows));
+
+    let failed = rows.iter().filter(|r| !r.passed()).count();
+    let checked = rows.iter().filter(|r| r.expected.is_some()).count();
+    println!("\n{failed} failed, {checked} checked against expected answers\n");
+    Ok(failed == 0)
+}
+
+fn run_dir(year: u64, day: u64, dir: &Path) -> Result<Vec<Row>, ValidateError> {
+    let problems: Vec<_> = find_problems(year, day).collect();
+    if problems.is_empty() {
+        return Err(ValidateError::NoSolutions { year, day });
+    }
+    let inputs = list_inputs(dir)?;
+    if inputs.is_empty() {
+        return Err(ValidateError::NoInputs(dir.to_path_buf()));
+    }
+
+    let mut rows = Vec::new();
+    for file in &inputs {
+        let name = file.file_stem().unwrap().to_string_lossy().into_owned();
+        let input =
+            std::fs::read_to_string(file).map_err(|e| ValidateError::Io(file.clone(), e))?;
+        let expected = expected_answers(&file.with_extension("answers"));
+
+        for p in &problems {
+            let (answer, time) = run_catching(p, &input);
+            let expected = expected.get(p.part as usize - 1).cloned().flatten();
+            rows.push(Row {
+                input: name.clone(),
+                part: p.part,
+                answer,
+                time,
+                expected,
+            });
+        }
+    }
+    Ok(rows)
+}
+
+struct Row {
+    input: String,
+    part: u64,
+    answer: Result<String, String>,
+    time: Duration,
+    expected: Option<String>,
+}
+
+impl Row {
+    fn passed(&self) -> bool {
+        match (&self.answer, &self.expected) {
+            (Err(_), _) => false,
+            (Ok(answer), Some(expected)) => answer == expected,
+            (Ok(_), None) => true,
+        }
+    }
+
+    fn check(&self) -> String {
+        match (&self.answer, &self.expected) {
+            (Err(_), _) => "panicked".to_string(),
+            (Ok(answer), Some(expected)) if answer == expected => "ok".to_string(),
+            (Ok(_), Some(expected)) => format!("expected {expected}"),
+            (Ok(_), None) => "-".to_string(),
+        }
+    }
+}
+
+fn render(rows: &[Row]) -> String {
+    let cells: Vec<[String; 5]> = rows
+        .iter()
+        .map(|r| {
+            let answer = match &r.answer {
+                Ok(answer) => answer.clone(),
+                Err(msg) => msg.clone(),
+            };
+            [
+                r.input.clone(),
+                r.part.to_string(),
+                answer,
+                humantime::format_duration(r.time).to_string(),
+                r.check(),
+            ]
+        })
+        .collect();
+
+    let header = ["Input", "Part", "Answer", "Time", "Check"];
+    let mut widths = header.map(str::len);
+    for row in &cells {
+        for (w, cell) in widths.iter_mut().zip(row) {
+            *w = (*w).max(cell.len());
+        }
+    }
+
+    let mut out = String::new();
+    for row in std::iter::once(header.map(String::from)).chain(cells) {
+        let line = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, w)| format!("{cell:<w$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        writeln!(out, "{}", line.trim_end()).unwrap();
+    }
+    out
+}
+
+fn find_problems(year: u64, day: u64) -> impl Iterator<Item = &'static Problem> {
+    let mut problems: Vec<_> = PROBLEMS
+        .iter()
+        .filter(|p| p.year == year && p.day == day)
+        .collect();
+    problems.sort_by_key(|p| p.part);
+    problems.into_iter()
+}
+
+fn list_inputs(dir: &Path) -> Result<Vec<PathBuf>, ValidateError> {
+    let io_err = |e| ValidateError::Io(dir.to_path_buf(), e);
+    let mut inputs = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(io_err)? {
+        let path = entry.map_err(io_err)?.path();
+        if path.extension().is_some_and(|ext| ext == "txt") {
+            inputs.push(path);
+        }
+    }
+    inputs.sort();
+    Ok(inputs)
+}
+
+fn expected_answers(path: &Path) -> Vec<Option<String>> {
+    let Ok(text) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    text.lines()
+        .map(|l| Some(l.trim().to_string()).filter(|l| !l.is_empty()))
+        .collect()
+}
+
+// The message comes from the payload, the panic hook is left alone as it's shared by every thread
+fn run_catching(p: &Problem, input: &str) -> (Result<String, String>, Duration) {
+    let time = Instant::now();
+    let result = panic::catch_unwind(|| (p.f)(input));
+    let elapsed = time.elapsed();
+
+    (result.map_err(|e| panic_message(&*e)), elapsed)
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    let msg = if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.as_str()
+    } else {
+        "unknown"
+    };
+    format!("panicked: {}", msg.lines().next().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Part 1 sums the numbers, part 2 counts them unless the input says boom
+    #[linkme::distributed_slice(PROBLEMS)]
+    static PART1: Problem = Problem {
+        year: 1,
+        day: 1,
+        part: 1,
+        f: |input| {
+            let sum: i64 = input
+                .split_whitespace()
+                .map(|n| n.parse::<i64>().unwrap())
+                .sum();
+            sum.to_string()
+        },
+        manifest_dir: env!("CARGO_MANIFEST_DIR"),
+    };
+    #[linkme::distributed_slice(PROBLEMS)]
+    static PART2: Problem = Problem {
+        year: 1,
+        day: 1,
+        part: 2,
+        f: |input| {
+            assert!(!input.contains("boom"), "boom");
+            input.split_whitespace().count().to_string()
+        },
+        manifest_dir: env!("CARGO_MANIFEST_DIR"),
+    };
+
+    // A fresh directory holding `(name, input, answers)`
+    fn inputs(test: &str, files: &[(&str, &str, Option<&str>)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc_validate_{test}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, input, answers) in files {
+            std::fs::write(dir.join(format!("{name}.txt")), input).unwrap();
+            if let Some(answers) = answers {
+                std::fs::write(dir.join(format!("{name}.answers")), answers).unwrap();
+            }
+        }
+        dir
+    }
+
+    fn checks(rows: &[Row]) -> Vec<(String, u64, String)> {
+        rows.iter()
+            .map(|r| (r.input.clone(), r.part, r.check()))
+            .collect()
+    }
+
+    #[test]
+    fn matching() {
+        let dir = inputs(
+            "matching",
+            &[("a", "1 2 3", Some("6\n3")), ("b", "10", None)],
+        );
+        let rows = run_dir(1, 1, &dir).unwrap();
+        assert_eq!(
+            checks(&rows),
+            [
+                ("a".into(), 1, "ok".into()),
+                ("a".into(), 2, "ok".into()),
+                ("b".into(), 1, "-".into()),
+                ("b".into(), 2, "-".into()),
+            ]
+        );
+        assert!(rows.iter().all(Row::passed));
+        assert!(validate_dir(1, 1, &dir).unwrap());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn mismatching() {
+        let dir = inputs("mismatching", &[("a", "1 2 3", Some("7\n3"))]);
+        let rows = run_dir(1, 1, &dir).unwrap();
+        assert_eq!(rows[0].answer, Ok("6".to_string()));
+        assert_eq!(rows[0].check(), "expected 7");
+        assert!(!rows[0].passed());
+        assert!(rows[1].passed());
+        assert!(!validate_dir(1, 1, &dir).unwrap());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn blank_answers() {
+        let dir = inputs(
+            "blank",
+            &[("a", "1 2", Some("\n2\n")), ("b", "4", Some("  "))],
+        );
+        let rows = run_dir(1, 1, &dir).unwrap();
+        let expected: Vec<_> = rows.iter().map(|r| r.expected.as_deref()).collect();
+        assert_eq!(expected, [None, Some("2"), None, None]);
+        assert!(validate_dir(1, 1, &dir).unwrap());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn panicking() {
+        let dir = inputs(
+            "panicking",
+            &[("a", "1 boom", None), ("b", "1 2", Some("3\n2"))],
+        );
+        let rows = run_dir(1, 1, &dir).unwrap();
+        // Part 1 fails to parse, part 2 trips its assert, and the next input still runs
+        assert_eq!(rows[0].check(), "panicked");
+        assert_eq!(rows[1].answer, Err("panicked: boom".to_string()));
+        assert_eq!(
+            checks(&rows)[2..],
+            [("b".into(), 1, "ok".into()), ("b".into(), 2, "ok".into())]
+        );
+        assert!(!validate_dir(1, 1, &dir).unwrap());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn empty_dir() {
+        let dir = inputs("empty", &[]);
+        std::fs::write(dir.join("notes.md"), "not an input").unwrap();
+        assert!(matches!(
+            validate_dir(1, 1, &dir),
+            Err(ValidateError::NoInputs(d)) if d == dir
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(
+            validate_dir(1, 1, &dir),
+            Err(ValidateError::Io(..))
+        ));
+        assert!(matches!(
+            validate_dir(1, 2, &dir),
+            Err(ValidateError::NoSolutions { year: 1, day: 2 })
+        ));
+    }
+
+    #[test]
+    fn profile_flag() {
+        let cli = Cli::try_parse_from(["aoc", "--profile", "alice"]).unwrap();
+        assert_eq!(cli.profile.as_deref(), Some("alice"));
+        let cli = Cli::try_parse_from(["aoc", "--profile=bob"]).unwrap();
+        assert_eq!(cli.profile.as_deref(), Some("bob"));
+        assert_eq!(Cli::try_parse_from(["aoc"]).unwrap().profile, None);
+
+        // Other arguments are rejected rather than scanned for a profile
+        assert!(Cli::try_parse_from(["aoc", "--name", "--profile"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "download", "--profile", "alice"]).is_err());
+    }
+
+    #[test]
+    fn validate_flag() {
+        let args = |args: &[&str]| {
+            Cli::try_parse_from(["aoc"].iter().chain(args))
+                .map_err(|e| e.to_string())
+                .and_then(|cli| cli.validate_args())
+        };
+        assert_eq!(args(&[]), Ok(None));
+        assert_eq!(
+            args(&["--validate", "2023", "18"]),
+            Ok(Some((2023, 18, None)))
+        );
+        assert_eq!(
+            args(&[
+                "--validate",
+                "2023",
+                "5",
+                "extra/inputs",
+                "--profile",
+                "alice"
+            ]),
+            Ok(Some((2023, 5, Some(PathBuf::from("extra/inputs")))))
+        );
+
+        assert!(args(&["--validate", "2023"]).is_err());
+        assert!(args(&["--validate", "2023", "1", "a", "b"]).is_err());
+        assert!(args(&["--validate", "2023", "day1"])
+            .unwrap_err()
+            .contains("invalid DAY 'day1'"));
+    }
+}
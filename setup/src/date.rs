@@ -0,0 +1,130 @@
+use time::{Date, Month, OffsetDateTime, UtcOffset};
+
+use crate::error::{Error, Result};
+
+pub const FIRST_YEAR: i32 = 2015;
+
+// Puzzles unlock at midnight EST
+pub fn unlock_offset() -> UtcOffset {
+    UtcOffset::from_hms(-5, 0, 0).unwrap()
+}
+
+pub fn now() -> OffsetDateTime {
+    OffsetDateTime::now_utc().to_offset(unlock_offset())
+}
+
+// The most recent event that has started
+pub fn latest_year(now: OffsetDateTime) -> i32 {
+    if now.month() == Month::December {
+        now.year()
+    } else {
+        now.year() - 1
+    }
+}
+
+// Since 2025 the event only runs for 12 days
+pub fn days_in_year(year: i32) -> u8 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+#[track_caller]
+pub fn unlock_time(year: i32, day: u8) -> OffsetDateTime {
+    checked_unlock_time(year, day).expect("Not a day in December")
+}
+
+// `None` when the day isn't in December, for days read from files or the site
+pub fn checked_unlock_time(year: i32, day: u8) -> Option<OffsetDateTime> {
+    let date = Date::from_calendar_date(year, Month::December, day).ok()?;
+    Some(date.midnight().assume_offset(unlock_offset()))
+}
+
+pub fn is_unlocked(year: i32, day: u8, now: OffsetDateTime) -> bool {
+    unlock_time(year, day) <= now
+}
+
+pub fn parse_year(s: &str) -> std::result::Result<i32, String> {
+    let year: i32 = s.parse().map_err(|_| format!("`{s}` is not a year"))?;
+    let current = now().year();
+    if (FIRST_YEAR..=current).contains(&year) {
+        Ok(year)
+    } else {
+        Err(format!("expected a year in {FIRST_YEAR}..={current}"))
+    }
+}
+
+pub fn validate(year: i32, day: u8) -> Result<()> {
+    let days = days_in_year(year);
+    if (1..=days).contains(&day) {
+        Ok(())
+    } else {
+        Err(Error::InvalidDate(format!(
+            "{year} only has {days} days, got day {day}"
+        )))
+    }
+}
+
+pub fn format_duration(duration: time::Duration) -> String {
+    let secs = duration.whole_seconds().max(0);
+    let (days, secs) = (secs / 86400, secs % 86400);
+    let (hours, mins, secs) = (secs / 3600, secs % 3600 / 60, secs % 60);
+
+    if days > 0 {
+        format!("{days}d {hours:02}:{mins:02}:{secs:02}")
+    } else {
+        format!("{hours:02}:{mins:02}:{secs:02}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlock() {
+        let unlock = unlock_time(2022, 5);
+        assert_eq!(unlock.to_offset(UtcOffset::UTC).hour(), 5);
+        assert!(is_unlocked(2022, 5, unlock));
+        assert!(!is_unlocked(2022, 5, unlock - time::Duration::SECOND));
+
+        assert_eq!(checked_unlock_time(2022, 5), Some(unlock));
+        assert_eq!(checked_unlock_time(2022, 0), None);
+        assert_eq!(checked_unlock_time(2022, 32), None);
+    }
+
+    #[test]
+    fn days() {
+        assert_eq!(days_in_year(2015), 25);
+        assert_eq!(days_in_year(2024), 25);
+        assert_eq!(days_in_year(2025), 12);
+    }
+
+    #[test]
+    fn latest() {
+        assert_eq!(latest_year(unlock_time(2023, 1)), 2023);
+        assert_eq!(
+            latest_year(unlock_time(2023, 1) - time::Duration::SECOND),
+            2022
+        );
+    }
+
+    #[test]
+    fn validate_day() {
+        assert!(validate(2024, 25).is_ok());
+        assert!(validate(2025, 12).is_ok());
+        assert!(validate(2025, 13).is_err());
+    }
+
+    #[test]
+    fn duration() {
+        assert_eq!(format_duration(time::Duration::seconds(59)), "00:00:59");
+        assert_eq!(format_duration(time::Duration::seconds(3723)), "01:02:03");
+        assert_eq!(
+            format_duration(time::Duration::seconds(90061)),
+            "1d 01:01:01"
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use std::{io::Write, thread, time::Duration};
+
+use clap::Args;
+use input_store::STORE_DIR;
+use reqwest::blocking::Client;
+use time::{Month, OffsetDateTime};
+
+use crate::{
+    date::{days_in_year, format_duration, is_unlocked, now, parse_year, unlock_time, validate},
+    error::{Error, Result},
+    get_token, paths,
+    site::{self, BASE_URL},
+    store,
+};
+
+// Minimum gap between two requests to the site when downloading in bulk
+const THROTTLE: Duration = Duration::from_secs(5);
+
+#[derive(Args, Debug)]
+pub struct DownloadArgs {
+    /// Puzzle year, defaults to the current one
+    #[arg(value_parser = parse_year)]
+    year: Option<i32>,
+    /// Puzzle day, defaults to today
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+    /// Puzzle year, same as the positional argument
+    #[arg(long = "year", value_name = "YEAR", conflicts_with = "year", value_parser = parse_year)]
+    year_flag: Option<i32>,
+    /// Puzzle day, same as the positional argument
+    #[arg(long = "day", value_name = "DAY", conflicts_with = "day", value_parser = clap::value_parser!(u8).range(1..=25))]
+    day_flag: Option<u8>,
+    /// Wait with a countdown until the puzzle unlocks, then download it
+    #[arg(long)]
+    wait: bool,
+    /// Download every unlocked day of the year
+    #[arg(long, conflicts_with_all = ["day", "day_flag", "wait"])]
+    all: bool,
+}
+
+impl DownloadArgs {
+    fn year(&self) -> Option<i32> {
+        self.year.or(self.year_flag)
+    }
+    fn day(&self) -> Option<u8> {
+        self.day.or(self.day_flag)
+    }
+}
+
+pub fn download(args: DownloadArgs) -> Result<()> {
+    let now = now();
+
+    if args.all {
+        let year = args.year().unwrap_or(now.year());
+        return download_year(year, now);
+    }
+
+    // When waiting without an explicit date, the target is the puzzle unlocking at the next midnight
+    let target = if args.wait {
+        now.date().next_day().unwrap()
+    } else {
+        now.date()
+    };
+    if args.day().is_none() && target.month() != Month::December {
+        return Err(Error::InvalidDate(format!(
+            "no puzzle unlocks on {target}, pass a year and day explicitly"
+        )));
+    }
+    let year = args.year().unwrap_or(target.year());
+    let day = args.day().unwrap_or(target.day());
+    validate(year, day)?;
+
+    if !is_unlocked(year, day, now) {
+        if args.wait {
+            wait_for_unlock(year, day)?;
+        } else {
+            return Err(Error::Locked {
+                year,
+                day,
+                remaining: format_duration(unlock_time(year, day) - now),
+            });
+        }
+    }
+
+    download_day(&client()?, year, day)
+}
+
+fn download_year(year: i32, now: OffsetDateTime) -> Result<()> {
+    let client = client()?;
+    let mut requested = false;
+
+    for day in 1..=days_in_year(year) {
+        if !is_unlocked(year, day, now) {
+            break;
+        }
+        if paths()?.input(year as u64, day as u64).exists() {
+            continue;
+        }
+        if requested {
+            thread::sleep(THROTTLE);
+        }
+        download_day(&client, year, day)?;
+        requested = true;
+    }
+    Ok(())
+}
+
+fn download_day(client: &Client, year: i32, day: u8) -> Result<()> {
+    let paths = paths()?;
+    let file = paths.input(year as u64, day as u64);
+
+    if !file.exists() {
+        let url = format!("{BASE_URL}/{year}/day/{day}/input");
+        let r = client.get(&url).send()?;
+        if !r.status().is_success() {
+            return Err(Error::Status {
+                url,
+                status: r.status(),
+            });
+        }
+        let input = r.text()?;
+        std::fs::create_dir_all(file.parent().unwrap())?;
+        std::fs::write(&file, &input)?;
+        println!("Downloaded {year}/day/{day} to {}", file.display());
+        if store::store_if_keyed(&paths, year, day, &input)? {
+            println!("Encrypted {year}/day/{day} into {STORE_DIR}");
+        }
+    } else {
+        println!("Input already downloaded at {}", file.display());
+    }
+    Ok(())
+}
+
+fn client() -> Result<Client> {
+    site::client(BASE_URL, &get_token()?)
+}
+
+fn wait_for_unlock(year: i32, day: u8) -> Result<()> {
+    let unlock = unlock_time(year, day);
+    let mut stdout = std::io::stdout();
+
+    loop {
+        let remaining = unlock - OffsetDateTime::now_utc();
+        if remaining <= time::Duration::ZERO {
+            break;
+        }
+        print!(
+            "\r{year}/day/{day} unlocks in {}",
+            format_duration(remaining)
+        );
+        stdout.flush()?;
+
+        let step = remaining.min(time::Duration::SECOND);
+        thread::sleep(step.try_into().unwrap());
+    }
+    println!();
+
+    // Give the server a moment so the first request doesn't land before the unlock
+    thread::sleep(Duration::from_secs(1));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(clap::Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: DownloadArgs,
+    }
+
+    fn args(s: &str) -> std::result::Result<DownloadArgs, clap::Error> {
+        use clap::Parser;
+        Cli::try_parse_from(std::iter::once("download").chain(s.split_whitespace())).map(|c| c.args)
+    }
+
+    #[test]
+    fn parse_args() {
+        let a = args("--year 2022 --all").unwrap();
+        assert_eq!(a.year(), Some(2022));
+        assert!(a.all);
+        assert!(!a.wait);
+
+        let a = args("2023 7 --wait").unwrap();
+        assert_eq!((a.year(), a.day()), (Some(2023), Some(7)));
+        assert!(a.wait);
+
+        assert!(args("2014").is_err());
+        assert!(args("2022 26").is_err());
+        assert!(args("2022 0").is_err());
+        assert!(args("2022 --year 2023").is_err());
+        assert!(args("2022 --all 4").is_err());
+    }
+}
@@ -0,0 +1,96 @@
+use std::fmt::Display;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    NoToken,
+    Keyring(keyring::Error),
+    Http(reqwest::Error),
+    Status {
+        url: String,
+        status: reqwest::StatusCode,
+    },
+    InvalidUrl(String),
+    InvalidResponse(String),
+    Io(std::io::Error),
+    Locked {
+        year: i32,
+        day: u8,
+        remaining: String,
+    },
+    InvalidDate(String),
+    NoKey,
+    KeyExists,
+    Store(input_store::Error),
+    Refused(String),
+    RateLimited(String),
+    InvalidFile(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NoToken => write!(f, "no session token stored, run `setup set <TOKEN>` first"),
+            Error::Keyring(e) => write!(f, "keyring error: {e}"),
+            Error::Http(e) => write!(f, "request failed: {e}"),
+            Error::Status { url, status } => write!(f, "request to {url} failed: {status}"),
+            Error::InvalidUrl(url) => write!(f, "invalid url `{url}`"),
+            Error::InvalidResponse(msg) => write!(f, "unexpected response: {msg}"),
+            Error::Io(e) => write!(f, "{e}"),
+            Error::Locked {
+                year,
+                day,
+                remaining,
+            } => write!(
+                f,
+                "{year}/day/{day} unlocks in {remaining}, use --wait to download it when it does"
+            ),
+            Error::InvalidDate(msg) => write!(f, "{msg}"),
+            Error::NoKey => write!(
+                f,
+                "no input key configured, run `setup key generate` or set {}",
+                input_store::KEY_VAR
+            ),
+            Error::KeyExists => write!(
+                f,
+                "an input key is already configured, pass --force to replace it"
+            ),
+            Error::Store(e) => write!(f, "{e}"),
+            Error::Refused(msg) => write!(f, "not submitting: {msg}"),
+            Error::InvalidFile(msg) => write!(f, "failed to read {msg}"),
+            Error::RateLimited(wait) => {
+                write!(f, "answered too recently, wait {wait} before trying again")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<keyring::Error> for Error {
+    fn from(value: keyring::Error) -> Self {
+        match value {
+            keyring::Error::NoEntry => Error::NoToken,
+            e => Error::Keyring(e),
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(value: reqwest::Error) -> Self {
+        Error::Http(value)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(value: std::io::Error) -> Self {
+        Error::Io(value)
+    }
+}
+
+impl From<input_store::Error> for Error {
+    fn from(value: input_store::Error) -> Self {
+        Error::Store(value)
+    }
+}
@@ -0,0 +1,353 @@
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use clap::Args;
+use input_store::Paths;
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+use crate::{
+    date::{checked_unlock_time, format_duration, latest_year, now, parse_year, validate},
+    error::{Error, Result},
+    get_token, paths,
+    site::{self, BASE_URL},
+};
+
+// The leaderboard API asks for at most one request every 15 minutes
+const CACHE_TIME: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Args, Debug)]
+pub struct LeaderboardArgs {
+    /// Leaderboard id, the number at the end of the leaderboard's url
+    id: u64,
+    /// Event year, defaults to the most recent one
+    #[arg(value_parser = parse_year)]
+    year: Option<i32>,
+    /// Only show part times for this day
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Leaderboard {
+    members: HashMap<String, Member>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Member {
+    id: u64,
+    name: Option<String>,
+    stars: u32,
+    local_score: u64,
+    #[serde(default)]
+    last_star_ts: i64,
+    completion_day_level: HashMap<u8, Progress>,
+}
+
+impl Member {
+    fn name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct Progress {
+    #[serde(rename = "1")]
+    part1: Star,
+    #[serde(rename = "2")]
+    part2: Option<Star>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Star {
+    get_star_ts: i64,
+}
+
+pub fn leaderboard(args: LeaderboardArgs) -> Result<()> {
+    let year = args.year.unwrap_or(latest_year(now()));
+    if let Some(day) = args.day {
+        validate(year, day)?;
+    }
+
+    let cache = cache_file(&paths()?, year, args.id);
+    let json = load(BASE_URL, get_token, year, args.id, &cache)?;
+    let board = parse(&json)?;
+
+    print!("{}", render(&board, args.id, year, args.day));
+    Ok(())
+}
+
+// Each profile sees the board through its own account, so they each get their own cache
+fn cache_file(paths: &Paths, year: i32, id: u64) -> PathBuf {
+    let dir = paths.root().join(".cache/leaderboard");
+    let dir = match paths.profile() {
+        Some(profile) => dir.join(profile),
+        None => dir,
+    };
+    dir.join(format!("{year}-{id}.json"))
+}
+
+fn load(
+    server: &str,
+    session: impl FnOnce() -> Result<String>,
+    year: i32,
+    id: u64,
+    cache: &Path,
+) -> Result<String> {
+    if let Some(json) = cached(cache)? {
+        return Ok(json);
+    }
+
+    let client = site::client(server, &session()?)?;
+    let json = fetch(&client, server, year, id)?;
+    // Only cache responses that are actually leaderboards
+    parse(&json)?;
+
+    if let Some(dir) = cache.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(cache, &json)?;
+    Ok(json)
+}
+
+fn cached(cache: &Path) -> Result<Option<String>> {
+    let Ok(meta) = std::fs::metadata(cache) else {
+        return Ok(None);
+    };
+    let age = meta.modified()?.elapsed().unwrap_or_default();
+    if age < CACHE_TIME {
+        Ok(Some(std::fs::read_to_string(cache)?))
+    } else {
+        Ok(None)
+    }
+}
+
+fn fetch(client: &Client, server: &str, year: i32, id: u64) -> Result<String> {
+    let url = format!("{server}/{year}/leaderboard/private/view/{id}.json");
+    let r = client.get(&url).send()?;
+    if !r.status().is_success() {
+        return Err(Error::Status {
+            url,
+            status: r.status(),
+        });
+    }
+    Ok(r.text()?)
+}
+
+fn parse(json: &str) -> Result<Leaderboard> {
+    serde_json::from_str(json).map_err(|e| {
+        Error::InvalidResponse(format!(
+            "{e}, check that the session token is valid and a member of the leaderboard"
+        ))
+    })
+}
+
+fn render(board: &Leaderboard, id: u64, year: i32, day: Option<u8>) -> String {
+    let mut members: Vec<_> = board.members.values().collect();
+    members.sort_by_key(|m| {
+        (
+            std::cmp::Reverse(m.local_score),
+            std::cmp::Reverse(m.stars),
+            m.last_star_ts,
+            m.id,
+        )
+    });
+    let width = members
+        .iter()
+        .map(|m| m.name().len())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+
+    let mut out = String::new();
+    writeln!(out, "Private leaderboard {id} - {year}\n").unwrap();
+    writeln!(out, "{:>3}  {:>5}  {:>5}  Name", "#", "Score", "Stars").unwrap();
+    for (rank, m) in members.iter().enumerate() {
+        writeln!(
+            out,
+            "{:>3}  {:>5}  {:>5}  {}",
+            rank + 1,
+            m.local_score,
+            m.stars,
+            m.name()
+        )
+        .unwrap();
+    }
+
+    let mut days: Vec<u8> = members
+        .iter()
+        .flat_map(|m| m.completion_day_level.keys().copied())
+        .filter(|&d| day.is_none_or(|day| d == day))
+        .collect();
+    days.sort();
+    days.dedup();
+
+    for d in days {
+        let unlock = checked_unlock_time(year, d);
+        // A timestamp out of range for a date or a day that isn't in December gets shown as `?`
+        let since_unlock = |star: &Star| match (
+            OffsetDateTime::from_unix_timestamp(star.get_star_ts),
+            unlock,
+        ) {
+            (Ok(ts), Some(unlock)) => format_duration(ts - unlock),
+            _ => "?".to_string(),
+        };
+
+        let mut solved: Vec<_> = members
+            .iter()
+            .filter_map(|m| Some((m, m.completion_day_level.get(&d)?)))
+            .collect();
+        solved.sort_by_key(|(m, p)| {
+            (
+                p.part2.as_ref().map_or(i64::MAX, |s| s.get_star_ts),
+                p.part1.get_star_ts,
+                m.id,
+            )
+        });
+
+        writeln!(out, "\nDay {d}").unwrap();
+        writeln!(
+            out,
+            "{:>3}  {:<width$}  {:>11}  {:>11}  {:>11}",
+            "#", "Name", "Part 1", "Part 2", "Delta"
+        )
+        .unwrap();
+        for (rank, (m, p)) in solved.into_iter().enumerate() {
+            let (part2, delta) = match &p.part2 {
+                Some(s) => (
+                    since_unlock(s),
+                    format_duration(time::Duration::seconds(s.get_star_ts - p.part1.get_star_ts)),
+                ),
+                None => ("-".to_string(), "-".to_string()),
+            };
+            writeln!(
+                out,
+                "{:>3}  {:<width$}  {:>11}  {:>11}  {:>11}",
+                rank + 1,
+                m.name(),
+                since_unlock(&p.part1),
+                part2,
+                delta
+            )
+            .unwrap();
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::site::test_server::serve_once;
+
+    const FIXTURE: &str = include_str!("leaderboard_test.json");
+
+    #[test]
+    fn fetch_and_cache() {
+        let dir = std::env::temp_dir().join(format!("aoc_leaderboard_{}", std::process::id()));
+        let cache = dir.join("2023-123.json");
+        let (server, handle) = serve_once("application/json", FIXTURE);
+
+        let session = || Ok("abc".to_string());
+        let json = load(&server, session, 2023, 123, &cache).unwrap();
+        assert_eq!(json, FIXTURE);
+
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("GET /2023/leaderboard/private/view/123.json "));
+        assert!(request.contains("session=abc"));
+
+        // The server is gone, so this has to come from the cache
+        let session = || panic!("cached leaderboard should not need a session");
+        let json = load(&server, session, 2023, 123, &cache).unwrap();
+        assert_eq!(json, FIXTURE);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn ranking() {
+        let board = parse(FIXTURE).unwrap();
+        let out = render(&board, 123, 2023, None);
+        let lines: Vec<_> = out.lines().collect();
+
+        assert_eq!(lines[0], "Private leaderboard 123 - 2023");
+        assert_eq!(lines[3], "  1     15      4  alice");
+        assert_eq!(lines[4], "  2      8      3  (anonymous user #1002)");
+        assert_eq!(lines[5], "  3      4      1  bob");
+        assert_eq!(lines[6], "  4      0      0  carol");
+    }
+
+    #[test]
+    fn day_times() {
+        let board = parse(FIXTURE).unwrap();
+        let out = render(&board, 123, 2023, Some(1));
+        let day: Vec<_> = out.lines().skip_while(|l| *l != "Day 1").collect();
+
+        assert_eq!(day.len(), 5);
+        assert!(day[2].starts_with("  1  alice "));
+        assert!(day[2].ends_with("   00:03:21     00:07:55     00:04:34"));
+        assert!(day[3].starts_with("  2  (anonymous user #1002) "));
+        assert!(day[3].ends_with("   00:05:00     01:05:00     01:00:00"));
+        assert!(day[4].starts_with("  3  bob "));
+        assert!(day[4].ends_with("   00:02:30            -            -"));
+        assert!(!out.contains("Day 2"));
+    }
+
+    #[test]
+    fn bad_timestamp() {
+        let mut board = parse(FIXTURE).unwrap();
+        for m in board.members.values_mut() {
+            for p in m.completion_day_level.values_mut() {
+                p.part1.get_star_ts = i64::MAX;
+            }
+        }
+        let out = render(&board, 123, 2023, Some(1));
+        let day: Vec<_> = out.lines().skip_while(|l| *l != "Day 1").collect();
+        assert!(day[4].ends_with("            ?            -            -"));
+    }
+
+    #[test]
+    fn bad_day() {
+        let mut board = parse(FIXTURE).unwrap();
+        for m in board.members.values_mut() {
+            if let Some(p) = m.completion_day_level.remove(&1) {
+                m.completion_day_level.insert(40, p);
+            }
+        }
+        let out = render(&board, 123, 2023, Some(40));
+        let day: Vec<_> = out.lines().skip_while(|l| *l != "Day 40").collect();
+        assert!(day.len() > 2);
+        assert!(day[2..].iter().all(|l| l.contains("            ?")));
+    }
+
+    #[test]
+    fn cache_per_profile() {
+        let paths = Paths::new(PathBuf::from("/ws"), PathBuf::from("input"));
+        assert_eq!(
+            cache_file(&paths, 2023, 123),
+            Path::new("/ws/.cache/leaderboard/2023-123.json")
+        );
+        let alice = paths.with_profile("alice").unwrap();
+        assert_eq!(
+            cache_file(&alice, 2023, 123),
+            Path::new("/ws/.cache/leaderboard/alice/2023-123.json")
+        );
+    }
+
+    #[test]
+    fn not_a_leaderboard() {
+        assert!(matches!(
+            parse("<!DOCTYPE html>"),
+            Err(Error::InvalidResponse(_))
+        ));
+    }
+}
@@ -1,58 +1,104 @@
-use std::sync::Arc;
+use std::{process::ExitCode, sync::OnceLock};
 
+use clap::{Parser, Subcommand};
+use input_store::Paths;
 use keyring::Entry;
-use reqwest::{cookie::Jar, Url};
-use time::{OffsetDateTime, UtcOffset};
-
-fn main() {
-    let mut args = std::env::args().skip(1);
-    let cmd = args.next().unwrap();
-
-    match cmd.as_str() {
-        "get" => println!("{}", get_token()),
-        "set" => set_token(args.next().unwrap()),
-        "download" => download(args),
-        _ => panic!(),
+
+use error::Result;
+
+mod date;
+mod download;
+mod error;
+mod leaderboard;
+mod site;
+mod store;
+mod submit;
+
+/// Manage the Advent of Code session token and download puzzle inputs
+#[derive(Parser)]
+#[command(name = "setup")]
+struct Cli {
+    /// Account to use, falls back to AOC_PROFILE. Each profile has its own token and inputs
+    #[arg(long, global = true)]
+    profile: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+static PROFILE: OnceLock<Option<String>> = OnceLock::new();
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the stored session token
+    Get,
+    /// Store the session token used to talk to adventofcode.com
+    Set { token: String },
+    /// Download puzzle inputs into the input directory as [{profile}/]{year}/day{day}.txt
+    Download(download::DownloadArgs),
+    /// Show a private leaderboard, fetched at most once every 15 minutes
+    Leaderboard(leaderboard::LeaderboardArgs),
+    /// Manage the key used to encrypt inputs
+    #[command(subcommand)]
+    Key(store::KeyCommand),
+    /// Encrypt every downloaded input into inputs.enc so it can be committed
+    Encrypt,
+    /// Restore missing inputs from inputs.enc
+    Decrypt,
+    /// Submit an answer, unless earlier guesses already rule it out
+    Submit(submit::SubmitArgs),
+    /// List the answers submitted for a day
+    Guesses(submit::GuessesArgs),
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    PROFILE.set(cli.profile).unwrap();
+
+    let result = match cli.command {
+        Command::Get => get_token().map(|t| println!("{t}")),
+        Command::Set { token } => set_token(token),
+        Command::Download(args) => download::download(args),
+        Command::Leaderboard(args) => leaderboard::leaderboard(args),
+        Command::Key(cmd) => store::key(cmd),
+        Command::Encrypt => store::encrypt(),
+        Command::Decrypt => store::decrypt(),
+        Command::Submit(args) => submit::submit(args),
+        Command::Guesses(args) => submit::guesses(args),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
     }
 }
 
-fn get_token() -> String {
-    let entry = keyring::Entry::new("aoc_runner", &whoami::username()).unwrap();
-    entry.get_password().expect("No token found")
+fn get_token() -> Result<String> {
+    Ok(token_entry()?.get_password()?)
+}
+
+fn set_token(token: String) -> Result<()> {
+    token_entry()?.set_password(&token)?;
+    Ok(())
 }
 
-fn set_token(token: String) {
+// The default account keeps the entry it always had, profiles get their own next to it
+fn token_entry() -> Result<Entry> {
     let user = whoami::username();
-    let entry = Entry::new("aoc_runner", &user).unwrap();
-    entry.set_password(&token).unwrap();
+    let entry = match paths()?.profile() {
+        Some(profile) => Entry::new("aoc_runner", &format!("{user}/{profile}"))?,
+        None => Entry::new("aoc_runner", &user)?,
+    };
+    Ok(entry)
 }
 
-fn download(mut args: impl Iterator<Item = String>) {
-    let today = OffsetDateTime::now_utc();
-    let today = today.to_offset(UtcOffset::from_hms(-5, 0, 0).unwrap());
-    let year = args
-        .next()
-        .map(|n| n.parse().unwrap())
-        .unwrap_or(today.year());
-    let day = args
-        .next()
-        .map(|n| n.parse().unwrap())
-        .unwrap_or(today.day());
-    let file = format!("input/{year}/day{day}.txt");
-    let session = get_token();
-
-    if std::fs::metadata(&file).is_err() {
-        let url = format!("https://adventofcode.com/{year}/day/{day}/input");
-        let cookies = Jar::default();
-        cookies.add_cookie_str(&format!("session={session}"), &url.parse::<Url>().unwrap());
-        let client = reqwest::blocking::Client::builder()
-            .cookie_provider(Arc::new(cookies))
-            .build()
-            .unwrap();
-        let r = client.get(url).send().unwrap();
-        std::fs::write(&file, r.text().unwrap()).unwrap();
-        println!("Downloaded {year}/day/{day} to {file}");
-    } else {
-        println!("Input already downloaded at {file}");
+// Everything setup writes is anchored at the workspace containing the current directory
+fn paths() -> Result<Paths> {
+    let paths = Paths::discover(&std::env::current_dir()?)?;
+    match PROFILE.get().and_then(|p| p.as_deref()) {
+        Some(profile) => Ok(paths.with_profile(profile)?),
+        None => Ok(paths),
     }
 }
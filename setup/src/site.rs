@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use reqwest::{blocking::Client, cookie::Jar, Url};
+
+use crate::error::{Error, Result};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+pub fn client(server: &str, session: &str) -> Result<Client> {
+    let cookies = Jar::default();
+    let url = server
+        .parse::<Url>()
+        .map_err(|_| Error::InvalidUrl(server.to_string()))?;
+    cookies.add_cookie_str(&format!("session={session}"), &url);
+    Ok(Client::builder()
+        .cookie_provider(Arc::new(cookies))
+        .build()?)
+}
+
+#[cfg(test)]
+pub mod test_server {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    // Serves a single request with `body` and hands back the raw request
+    pub fn serve_once(
+        content_type: &'static str,
+        body: &'static str,
+    ) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let request = read_request(&mut stream);
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            request
+        });
+        (format!("http://{addr}"), handle)
+    }
+
+    fn read_request(stream: &mut impl Read) -> String {
+        let mut request = Vec::new();
+        let mut buf = [0; 4096];
+        loop {
+            let n = stream.read(&mut buf).unwrap();
+            request.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&request);
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let len = head
+                    .lines()
+                    .find_map(|l| {
+                        l.to_ascii_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|v| v.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                if body.len() >= len {
+                    return text.into_owned();
+                }
+            }
+            if n == 0 {
+                return text.into_owned();
+            }
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::path::Path;
+
+use clap::Subcommand;
+use input_store::{Key, Paths, STORE_DIR};
+
+use crate::{
+    error::{Error, Result},
+    paths,
+};
+
+#[derive(Subcommand, Debug)]
+pub enum KeyCommand {
+    /// Generate a new key, store it in the keyring and print it so it can be shared
+    Generate {
+        /// Replace the key that is already configured
+        #[arg(long)]
+        force: bool,
+    },
+    /// Store a key someone else generated
+    Set { key: String },
+    /// Print the key in use
+    Get,
+}
+
+pub fn key(cmd: KeyCommand) -> Result<()> {
+    match cmd {
+        KeyCommand::Generate { force } => {
+            if !force && Key::load()?.is_some() {
+                return Err(Error::KeyExists);
+            }
+            let key = Key::generate();
+            key.save()?;
+            println!("{}", key.to_hex());
+        }
+        KeyCommand::Set { key } => Key::from_hex(&key)?.save()?,
+        KeyCommand::Get => println!("{}", require_key()?.to_hex()),
+    }
+    Ok(())
+}
+
+pub fn encrypt() -> Result<()> {
+    let n = encrypt_all(&paths()?, &require_key()?)?;
+    println!("Encrypted {n} inputs into {STORE_DIR}");
+    Ok(())
+}
+
+pub fn decrypt() -> Result<()> {
+    let n = decrypt_all(&paths()?, &require_key()?)?;
+    println!("Decrypted {n} inputs from {STORE_DIR}");
+    Ok(())
+}
+
+// Keeps the store in sync with a freshly downloaded input when a key is configured. A key that is
+// set but can't be read is an error rather than a reason to quietly skip the store
+pub fn store_if_keyed(paths: &Paths, year: i32, day: u8, input: &str) -> Result<bool> {
+    match Key::load()? {
+        Some(key) => {
+            input_store::write(paths, &key, year as u64, day as u64, input)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+fn require_key() -> Result<Key> {
+    Key::load()?.ok_or(Error::NoKey)
+}
+
+fn encrypt_all(paths: &Paths, key: &Key) -> Result<usize> {
+    let mut count = 0;
+    for (year, day) in list(&paths.input_dir(), ".txt")? {
+        let input = std::fs::read_to_string(paths.input(year, day))?;
+        if input_store::read(paths, key, year, day).is_ok_and(|stored| stored == input) {
+            continue;
+        }
+        input_store::write(paths, key, year, day, &input)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+fn decrypt_all(paths: &Paths, key: &Key) -> Result<usize> {
+    let mut count = 0;
+    for (year, day) in list(&paths.store_dir(), "")? {
+        let path = paths.input(year, day);
+        if path.exists() {
+            continue;
+        }
+        let input = input_store::read(paths, key, year, day)?;
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(path, input)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+// Finds every `{dir}/{year}/day{day}{suffix}`
+fn list(dir: &Path, suffix: &str) -> Result<Vec<(u64, u64)>> {
+    let mut out = Vec::new();
+    let Ok(years) = std::fs::read_dir(dir) else {
+        return Ok(out);
+    };
+
+    for year in years {
+        let year = year?;
+        let Some(y) = year.file_name().to_str().and_then(|y| y.parse().ok()) else {
+            continue;
+        };
+        for day in std::fs::read_dir(year.path())? {
+            let name = day?.file_name();
+            let d = name
+                .to_str()
+                .and_then(|n| n.strip_prefix("day"))
+                .and_then(|n| n.strip_suffix(suffix))
+                .and_then(|n| n.parse().ok());
+            if let Some(d) = d {
+                out.push((y, d));
+            }
+        }
+    }
+    out.sort();
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sync() {
+        let root = std::env::temp_dir().join(format!("aoc_setup_store_{}", std::process::id()));
+        let paths = Paths::new(root.clone(), "puzzles".into());
+        let key = Key::generate();
+
+        for (year, day, input) in [(2022, 1, "1\n2\n"), (2023, 12, "???.### 1,1,3\n")] {
+            let path = paths.input(year, day);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, input).unwrap();
+        }
+        std::fs::write(root.join("puzzles/2023/notes.md"), "").unwrap();
+
+        assert_eq!(encrypt_all(&paths, &key).unwrap(), 2);
+        assert_eq!(encrypt_all(&paths, &key).unwrap(), 0);
+
+        std::fs::remove_dir_all(paths.input_dir()).unwrap();
+        assert_eq!(decrypt_all(&paths, &key).unwrap(), 2);
+        assert_eq!(
+            std::fs::read_to_string(paths.input(2023, 12)).unwrap(),
+            "???.### 1,1,3\n"
+        );
+        assert_eq!(decrypt_all(&paths, &key).unwrap(), 0);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
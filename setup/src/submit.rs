@@ -0,0 +1,411 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use clap::Args;
+use input_store::Paths;
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::{
+    date::{checked_unlock_time, format_duration, now, parse_year, unlock_time, validate},
+    error::{Error, Result},
+    get_token, paths,
+    site::{self, BASE_URL},
+};
+
+#[derive(Args, Debug)]
+pub struct SubmitArgs {
+    #[arg(value_parser = parse_year)]
+    year: i32,
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+    answer: String,
+    /// Submit even if the guess history says the answer is wrong
+    #[arg(long)]
+    force: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct GuessesArgs {
+    #[arg(value_parser = parse_year)]
+    year: i32,
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct Guess {
+    year: i32,
+    day: u8,
+    part: u8,
+    answer: String,
+    verdict: Verdict,
+    submitted_at: i64,
+    // Seconds between the puzzle unlocking and a correct answer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    solve_time: Option<i64>,
+}
+
+impl Guess {
+    // `?` when a hand edited history holds a timestamp or day that isn't a date
+    fn since_unlock(&self) -> String {
+        match (
+            OffsetDateTime::from_unix_timestamp(self.submitted_at),
+            checked_unlock_time(self.year, self.day),
+        ) {
+            (Ok(at), Some(unlock)) => format_duration(at - unlock),
+            _ => "?".to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default, Debug)]
+struct History {
+    guesses: Vec<Guess>,
+}
+
+impl History {
+    fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|e| Error::InvalidFile(format!("{}: {e}", path.display()))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self).unwrap())?;
+        Ok(())
+    }
+
+    fn guesses(&self, year: i32, day: u8, part: u8) -> impl Iterator<Item = &Guess> {
+        self.guesses
+            .iter()
+            .filter(move |g| g.year == year && g.day == day && g.part == part)
+    }
+
+    // Refuses answers the site has already ruled out
+    fn check(&self, year: i32, day: u8, part: u8, answer: &str) -> Result<()> {
+        let refuse = |msg: String| Err(Error::Refused(msg));
+
+        if let Some(g) = self
+            .guesses(year, day, part)
+            .find(|g| g.verdict == Verdict::Correct)
+        {
+            return refuse(format!("part {part} is already solved with {}", g.answer));
+        }
+        if let Some(g) = self.guesses(year, day, part).find(|g| g.answer == answer) {
+            return refuse(format!(
+                "{answer} was already guessed and was {}",
+                g.verdict
+            ));
+        }
+
+        let Ok(n) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+        let bound = |verdict| {
+            self.guesses(year, day, part)
+                .filter(move |g| g.verdict == verdict)
+                .filter_map(|g| g.answer.parse::<i128>().ok())
+        };
+        if let Some(high) = bound(Verdict::TooHigh).min() {
+            if n >= high {
+                return refuse(format!("{answer} is too high, {high} already was"));
+            }
+        }
+        if let Some(low) = bound(Verdict::TooLow).max() {
+            if n <= low {
+                return refuse(format!("{answer} is too low, {low} already was"));
+            }
+        }
+        Ok(())
+    }
+
+    fn record(
+        &mut self,
+        year: i32,
+        day: u8,
+        part: u8,
+        answer: String,
+        verdict: Verdict,
+        at: OffsetDateTime,
+    ) -> &Guess {
+        let solve_time =
+            (verdict == Verdict::Correct).then(|| (at - unlock_time(year, day)).whole_seconds());
+        self.guesses.push(Guess {
+            year,
+            day,
+            part,
+            answer,
+            verdict,
+            submitted_at: at.unix_timestamp(),
+            solve_time,
+        });
+        self.guesses.last().unwrap()
+    }
+}
+
+pub fn submit(args: SubmitArgs) -> Result<()> {
+    validate(args.year, args.day)?;
+    let answer = args.answer.trim().to_string();
+    let path = history_file(&paths()?);
+
+    let mut history = History::load(&path)?;
+    if !args.force {
+        history.check(args.year, args.day, args.part, &answer)?;
+    }
+
+    let client = site::client(BASE_URL, &get_token()?)?;
+    let submitted_at = now();
+    let verdict = post(&client, BASE_URL, args.year, args.day, args.part, &answer)?;
+
+    let guess = history.record(
+        args.year,
+        args.day,
+        args.part,
+        answer,
+        verdict,
+        submitted_at,
+    );
+    match guess.solve_time {
+        Some(secs) => println!(
+            "{} is correct, solved {} after unlock",
+            guess.answer,
+            format_duration(time::Duration::seconds(secs))
+        ),
+        None => println!("{} is {}", guess.answer, guess.verdict),
+    }
+    history.save(&path)
+}
+
+pub fn guesses(args: GuessesArgs) -> Result<()> {
+    let history = History::load(&history_file(&paths()?))?;
+    for part in 1..=2 {
+        for g in history.guesses(args.year, args.day, part) {
+            println!(
+                "part {part}  {:>11}  {:<8}  {}",
+                g.since_unlock(),
+                g.verdict.to_string(),
+                g.answer
+            );
+        }
+    }
+    Ok(())
+}
+
+// Answers differ between accounts, so each profile keeps its own history
+fn history_file(paths: &Paths) -> PathBuf {
+    let name = match paths.profile() {
+        Some(profile) => format!("guesses-{profile}.json"),
+        None => "guesses.json".to_string(),
+    };
+    paths.root().join(".cache").join(name)
+}
+
+fn post(
+    client: &Client,
+    server: &str,
+    year: i32,
+    day: u8,
+    part: u8,
+    answer: &str,
+) -> Result<Verdict> {
+    let url = format!("{server}/{year}/day/{day}/answer");
+    let r = client
+        .post(&url)
+        .form(&[("level", part.to_string()), ("answer", answer.to_string())])
+        .send()?;
+    if !r.status().is_success() {
+        return Err(Error::Status {
+            url,
+            status: r.status(),
+        });
+    }
+    parse_verdict(&r.text()?)
+}
+
+fn parse_verdict(html: &str) -> Result<Verdict> {
+    if html.contains("That's the right answer") {
+        Ok(Verdict::Correct)
+    } else if html.contains("That's not the right answer") {
+        if html.contains("your answer is too high") {
+            Ok(Verdict::TooHigh)
+        } else if html.contains("your answer is too low") {
+            Ok(Verdict::TooLow)
+        } else {
+            Ok(Verdict::Wrong)
+        }
+    } else if html.contains("You gave an answer too recently") {
+        let wait = html
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .map_or("a while", |(wait, _)| wait);
+        Err(Error::RateLimited(wait.to_string()))
+    } else if html.contains("You don't seem to be solving the right level") {
+        Err(Error::Refused(
+            "the site says this part is already solved or still locked".to_string(),
+        ))
+    } else {
+        Err(Error::InvalidResponse(
+            "couldn't find a verdict in the page".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::site::test_server::serve_once;
+
+    fn history(guesses: &[(&str, Verdict)]) -> History {
+        let mut h = History::default();
+        let at = unlock_time(2023, 3) + time::Duration::minutes(5);
+        for (answer, verdict) in guesses {
+            h.record(2023, 3, 1, answer.to_string(), *verdict, at);
+        }
+        h
+    }
+
+    #[test]
+    fn repeats() {
+        let h = history(&[("abc", Verdict::Wrong), ("100", Verdict::Wrong)]);
+        assert!(h.check(2023, 3, 1, "abc").is_err());
+        assert!(h.check(2023, 3, 1, "100").is_err());
+        assert!(h.check(2023, 3, 1, "101").is_ok());
+        assert!(h.check(2023, 3, 2, "abc").is_ok());
+        assert!(h.check(2023, 4, 1, "abc").is_ok());
+    }
+
+    #[test]
+    fn bounds() {
+        let h = history(&[
+            ("500", Verdict::TooHigh),
+            ("900", Verdict::TooHigh),
+            ("100", Verdict::TooLow),
+            ("50", Verdict::TooLow),
+        ]);
+        assert!(h.check(2023, 3, 1, "499").is_ok());
+        assert!(h.check(2023, 3, 1, "101").is_ok());
+        assert!(h.check(2023, 3, 1, "500").is_err());
+        assert!(h.check(2023, 3, 1, "700").is_err());
+        assert!(h.check(2023, 3, 1, "100").is_err());
+        assert!(h.check(2023, 3, 1, "-3").is_err());
+        assert!(h.check(2023, 3, 1, "abc").is_ok());
+    }
+
+    #[test]
+    fn solved() {
+        let mut h = history(&[("12", Verdict::TooLow)]);
+        let at = unlock_time(2023, 3) + time::Duration::seconds(754);
+        let g = h.record(2023, 3, 1, "42".to_string(), Verdict::Correct, at);
+        assert_eq!(g.solve_time, Some(754));
+        assert!(h.check(2023, 3, 1, "43").is_err());
+        assert_eq!(h.guesses[0].solve_time, None);
+    }
+
+    #[test]
+    fn since_unlock() {
+        let h = history(&[("abc", Verdict::Wrong)]);
+        let mut g = h.guesses[0].clone();
+        assert_eq!(g.since_unlock(), "00:05:00");
+        g.submitted_at = i64::MAX;
+        assert_eq!(g.since_unlock(), "?");
+        g = h.guesses[0].clone();
+        g.day = 40;
+        assert_eq!(g.since_unlock(), "?");
+    }
+
+    #[test]
+    fn history_file() {
+        let dir = std::env::temp_dir().join(format!("aoc_guesses_{}", std::process::id()));
+        let path = dir.join("guesses.json");
+        assert!(History::load(&path).unwrap().guesses.is_empty());
+
+        history(&[("7", Verdict::TooHigh)]).save(&path).unwrap();
+        let h = History::load(&path).unwrap();
+        assert_eq!(h.guesses[0].verdict, Verdict::TooHigh);
+        assert!(h.check(2023, 3, 1, "8").is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn verdicts() {
+        let page = |s: &str| format!("<main><article><p>{s}</p></article></main>");
+        assert_eq!(
+            parse_verdict(&page(
+                "That's the right answer! You are one gold star closer."
+            ))
+            .unwrap(),
+            Verdict::Correct
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "That's not the right answer; your answer is too high."
+            ))
+            .unwrap(),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "That's not the right answer; your answer is too low."
+            ))
+            .unwrap(),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            parse_verdict(&page("That's not the right answer. If you're stuck...")).unwrap(),
+            Verdict::Wrong
+        );
+        assert!(matches!(
+            parse_verdict(&page("You gave an answer too recently. You have 37s left to wait.")),
+            Err(Error::RateLimited(w)) if w == "37s"
+        ));
+    }
+
+    #[test]
+    fn post_answer() {
+        let (server, handle) = serve_once(
+            "text/html",
+            "<article><p>That's not the right answer; your answer is too low.</p></article>",
+        );
+        let client = site::client(&server, "abc").unwrap();
+        let verdict = post(&client, &server, 2022, 4, 2, "1234").unwrap();
+        assert_eq!(verdict, Verdict::TooLow);
+
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("POST /2022/day/4/answer "));
+        assert!(request.contains("session=abc"));
+        assert!(request.ends_with("level=2&answer=1234"));
+    }
+}
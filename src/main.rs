@@ -3,6 +3,9 @@ mod y2023;
 mod y2022;
 
 fn main() {
+    if runner::validate_from_args() {
+        return;
+    }
     //runner::run_year(2022);
     runner::run_problem(2023, 18);
     //runner::run_recent()
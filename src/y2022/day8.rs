@@ -4,19 +4,6 @@ use runner::aoc;
 
 use aoc_lib::{Coord, Grid};
 
-fn parse(input: &str) -> Grid<i64> {
-    let mut grid = Grid::build();
-    for l in input.trim().lines().map(str::trim) {
-        let mut row = Vec::new();
-        for c in l.chars() {
-            let n = c as u8 - b'0';
-            row.push(n as i64);
-        }
-        grid.push_row(row)
-    }
-    grid.finish()
-}
-
 #[derive(Hash, PartialEq, Eq)]
 struct State {
     location: Coord,
@@ -85,7 +72,7 @@ fn scenic_score(grid: &Grid<i64>, loc: Coord) -> i64 {
 
 #[aoc(day8, part1)]
 fn part1(input: &str) -> usize {
-    let grid = parse(input);
+    let grid = Grid::parse_digits(input);
     let mut cache = HashMap::new();
     grid.coords()
         .map(|c| visible(&mut cache, &grid, c))
@@ -95,7 +82,7 @@ fn part1(input: &str) -> usize {
 
 #[aoc(day8, part2)]
 fn part2(input: &str) -> i64 {
-    let grid = parse(input);
+    let grid = Grid::parse_digits(input);
     grid.coords().map(|c| scenic_score(&grid, c)).max().unwrap()
 }
 
@@ -1,5 +1,6 @@
-use std::ops::{Add, Index, IndexMut, Sub};
+use std::ops::{Index, IndexMut};
 
+use aoc_lib::{direction::Direction8, Coord};
 use runner::aoc;
 
 #[derive(Debug)]
@@ -38,7 +39,7 @@ impl Grid {
         for r in 0..rows {
             for c in 0..cols {
                 if self.cells[r][c].ty == Element::Start {
-                    return Coord::new(r as isize, c as isize);
+                    return Coord::new(r as i64, c as i64);
                 }
             }
         }
@@ -52,31 +53,31 @@ impl Grid {
         let mut matches = Vec::new();
 
         if matches!(
-            self.get(start.north()).map(|c| c.ty),
+            self.get(start + Coord::N).map(|c| c.ty),
             Some(Element::Vertical) | Some(Element::SE) | Some(Element::SW)
         ) {
-            matches.push(start.north());
+            matches.push(start + Coord::N);
         }
 
         if matches!(
-            self.get(start.south()).map(|c| c.ty),
+            self.get(start + Coord::S).map(|c| c.ty),
             Some(Element::Vertical) | Some(Element::NE) | Some(Element::NW)
         ) {
-            matches.push(start.south())
+            matches.push(start + Coord::S)
         }
 
         if matches!(
-            self.get(start.east()).map(|c| c.ty),
+            self.get(start + Coord::E).map(|c| c.ty),
             Some(Element::Horizontal) | Some(Element::NW) | Some(Element::SW)
         ) {
-            matches.push(start.east())
+            matches.push(start + Coord::E)
         }
 
         if matches!(
-            self.get(start.west()).map(|c| c.ty),
+            self.get(start + Coord::W).map(|c| c.ty),
             Some(Element::Horizontal) | Some(Element::NE) | Some(Element::SE)
         ) {
-            matches.push(start.west())
+            matches.push(start + Coord::W)
         }
 
         assert!(matches.len() == 2);
@@ -167,114 +168,18 @@ impl Element {
 
     // Row Col
     fn offsets(self) -> [Coord; 2] {
-        let zero = Coord::new(0, 0);
-
         match self {
-            Element::Vertical => [zero.north(), zero.south()],
-            Element::Horizontal => [zero.east(), zero.west()],
-            Element::NE => [zero.north(), zero.east()],
-            Element::NW => [zero.north(), zero.west()],
-            Element::SW => [zero.south(), zero.west()],
-            Element::SE => [zero.south(), zero.east()],
+            Element::Vertical => [Coord::N, Coord::S],
+            Element::Horizontal => [Coord::E, Coord::W],
+            Element::NE => [Coord::N, Coord::E],
+            Element::NW => [Coord::N, Coord::W],
+            Element::SW => [Coord::S, Coord::W],
+            Element::SE => [Coord::S, Coord::E],
             _ => panic!("No valid offsets for {self:?}"),
         }
     }
 }
 
-#[derive(PartialEq, Eq, Debug, Clone, Copy)]
-struct Coord {
-    row: isize,
-    col: isize,
-}
-
-impl Coord {
-    const ZERO: Self = Self::new(0, 0);
-
-    const N: Self = Self::ZERO.north();
-    const NE: Self = Self::ZERO.north().east();
-    const E: Self = Self::ZERO.east();
-    const SE: Self = Self::ZERO.south().east();
-    const S: Self = Self::ZERO.south();
-    const SW: Self = Self::ZERO.south().west();
-    const W: Self = Self::ZERO.west();
-    const NW: Self = Self::ZERO.north().west();
-
-    const fn new(row: isize, col: isize) -> Self {
-        Self { row, col }
-    }
-
-    const fn north(mut self) -> Self {
-        self.row -= 1;
-        self
-    }
-
-    const fn south(mut self) -> Self {
-        self.row += 1;
-        self
-    }
-
-    const fn east(mut self) -> Self {
-        self.col += 1;
-        self
-    }
-
-    const fn west(mut self) -> Self {
-        self.col -= 1;
-        self
-    }
-
-    fn offset(self, d: Direction) -> Self {
-        match d {
-            Direction::N => self.north(),
-            Direction::NE => self.north().east(),
-            Direction::E => self.east(),
-            Direction::SE => self.south().east(),
-            Direction::S => self.south(),
-            Direction::SW => self.south().west(),
-            Direction::W => self.west(),
-            Direction::NW => self.north().west(),
-        }
-    }
-
-    fn dir(self, next: Coord) -> Direction {
-        let diff = next - self;
-
-        match diff {
-            Self::N => Direction::N,
-            Self::NE => Direction::NE,
-            Self::E => Direction::E,
-            Self::SE => Direction::SE,
-            Self::S => Direction::S,
-            Self::SW => Direction::SW,
-            Self::W => Direction::W,
-            Self::NW => Direction::NW,
-            _ => panic!(),
-        }
-    }
-}
-
-impl Add for Coord {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            row: self.row + rhs.row,
-            col: self.col + rhs.col,
-        }
-    }
-}
-
-impl Sub for Coord {
-    type Output = Self;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self {
-            row: self.row - rhs.row,
-            col: self.col - rhs.col,
-        }
-    }
-}
-
 #[aoc(day10, part1)]
 fn part1(input: &str) -> u64 {
     let mut grid = Grid::parse(input);
@@ -312,67 +217,17 @@ fn part1(input: &str) -> u64 {
         .unwrap()
 }
 
-#[repr(i64)]
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-enum Direction {
-    N = 0,
-    NE,
-    E,
-    SE,
-    S,
-    SW,
-    W,
-    NW,
+// The directions passed turning clockwise from `from` until reaching `to`
+fn sweep_cw(from: Direction8, to: Direction8) -> Vec<Direction8> {
+    std::iter::successors(Some(from.turn_right()), |d| Some(d.turn_right()))
+        .take_while(|&d| d != to.turn_right())
+        .collect()
 }
 
-impl Direction {
-    fn from_i64(i: i64) -> Self {
-        match i {
-            0 => Self::N,
-            1 => Self::NE,
-            2 => Self::E,
-            3 => Self::SE,
-            4 => Self::S,
-            5 => Self::SW,
-            6 => Self::W,
-            7 => Self::NW,
-            _ => {
-                panic!()
-            }
-        }
-    }
-
-    fn sweep_cw(self, to: Direction) -> Vec<Direction> {
-        let mut out = Vec::new();
-
-        let mut pos = self as i64;
-
-        while pos != to as i64 {
-            pos += 1;
-            if pos >= 8 {
-                pos = 0;
-            }
-            out.push(Direction::from_i64(pos));
-        }
-
-        out
-    }
-
-    fn sweep_ccw(self, to: Direction) -> Vec<Direction> {
-        let mut out = Vec::new();
-
-        let mut pos = self as i64;
-
-        while pos != to as i64 {
-            pos -= 1;
-            if pos < 0 {
-                pos = 7;
-            }
-            out.push(Direction::from_i64(pos));
-        }
-
-        out
-    }
+fn sweep_ccw(from: Direction8, to: Direction8) -> Vec<Direction8> {
+    std::iter::successors(Some(from.turn_left()), |d| Some(d.turn_left()))
+        .take_while(|&d| d != to.turn_left())
+        .collect()
 }
 
 #[aoc(day10, part2)]
@@ -402,18 +257,18 @@ fn part2(input: &str) -> u64 {
     while grid[current].ty != Element::Start {
         let next = grid.find_next(current, last);
 
-        let dir = current.dir(last);
-        let dir_n = current.dir(next);
+        let dir = Direction8::from_coord(last - current).unwrap();
+        let dir_n = Direction8::from_coord(next - current).unwrap();
 
-        let s1 = dir.sweep_cw(dir_n);
+        let s1 = sweep_cw(dir, dir_n);
 
         for d in s1 {
-            let c = current.offset(d);
+            let c = current + d.to_coord();
             flood(&mut grid, Sector::S1, c);
         }
-        let s2 = dir.sweep_ccw(dir_n);
+        let s2 = sweep_ccw(dir, dir_n);
         for d in s2 {
-            let c = current.offset(d);
+            let c = current + d.to_coord();
             flood(&mut grid, Sector::S2, c);
         }
 
@@ -449,10 +304,9 @@ fn flood(grid: &mut Grid, value: Sector, coord: Coord) {
 
     grid[coord].sector = value;
 
-    flood(grid, value, coord.north());
-    flood(grid, value, coord.south());
-    flood(grid, value, coord.east());
-    flood(grid, value, coord.west());
+    for n in coord.neighbors4() {
+        flood(grid, value, n);
+    }
 }
 
 fn vis(grid: &Grid) {
@@ -64,7 +64,7 @@ impl Spring {
             if index > 0 && parts[index - 1] == Part::Broken {
                 return false;
             }
-            if last && parts[index + len..].iter().any(|&p| p == Part::Broken) {
+            if last && parts[index + len..].contains(&Part::Broken) {
                 return false;
             }
 
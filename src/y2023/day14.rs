@@ -1,151 +1,67 @@
+use aoc_lib::{cycle::nth_state, Direction, Grid};
+use rayon::prelude::*;
 use runner::aoc;
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum Direction {
-    N,
-    S,
-    E,
-    W,
-}
-
-impl Direction {
-    fn next(self, mut row: usize, mut col: usize) -> (usize, usize) {
-        match self {
-            Direction::N => row = row.saturating_sub(1),
-            Direction::S => row += 1,
-            Direction::W => col = col.saturating_sub(1),
-            Direction::E => col += 1,
-        }
-
-        (row, col)
-    }
-
-    fn on_edge(self, row: usize, col: usize, rows: usize, cols: usize) -> bool {
-        match self {
-            Direction::N => row == 0,
-            Direction::E => col == cols - 1,
-            Direction::S => row == rows - 1,
-            Direction::W => col == 0,
-        }
-    }
-}
-
-#[derive(PartialEq, Eq, Clone)]
-struct Grid {
-    rows: Vec<Vec<char>>,
-}
-
-impl Grid {
-    fn parse(input: &str) -> Self {
-        let mut rows = Vec::new();
-        for l in input.trim().lines().map(str::trim) {
-            let mut row = Vec::new();
-            for c in l.chars() {
-                row.push(c);
-            }
-            rows.push(row)
-        }
-
-        Self { rows }
-    }
-
-    fn next_free(&self, mut row: usize, mut col: usize, dir: Direction) -> (usize, usize) {
-        let rows = self.rows.len();
-        let cols = self.rows[0].len();
-
-        assert!(self.rows[row][col] == 'O');
-
-        while !dir.on_edge(row, col, rows, cols) {
-            let (new_row, new_col) = dir.next(row, col);
-            if self.rows[new_row][new_col] != '.' {
-                return (row, col);
-            }
-            (row, col) = (new_row, new_col);
-        }
-        (row, col)
-    }
-
-    fn slide(&mut self, dir: Direction) {
-        let rows = self.rows.len();
-        let cols = self.rows[0].len();
-
-        let mut row_iter = Box::new(0..rows) as Box<dyn Iterator<Item = usize>>;
-        let mut col_iter: Box<dyn Fn() -> Box<dyn Iterator<Item = usize>>> =
-            Box::new(|| Box::new(0..cols) as Box<dyn Iterator<Item = usize>>);
-
-        match dir {
-            Direction::S => row_iter = Box::new((0..rows).rev()) as Box<dyn Iterator<Item = usize>>,
-            Direction::E => {
-                col_iter = Box::new(|| Box::new((0..cols).rev()) as Box<dyn Iterator<Item = usize>>)
+// Rolls every round rock as far towards the front of `cells` as it goes
+fn roll<'a>(cells: impl Iterator<Item = &'a mut char>) {
+    let mut cells: Vec<_> = cells.collect();
+    let mut free = 0;
+    for i in 0..cells.len() {
+        match *cells[i] {
+            '#' => free = i + 1,
+            'O' => {
+                *cells[i] = '.';
+                *cells[free] = 'O';
+                free += 1;
             }
             _ => {}
         }
-
-        for row in row_iter {
-            for col in (col_iter)() {
-                if self.rows[row][col] == 'O' {
-                    let (new_row, new_col) = self.next_free(row, col, dir);
-                    self.rows[row][col] = '.';
-                    self.rows[new_row][new_col] = 'O';
-                }
-            }
-        }
     }
+}
 
-    fn moment(&self) -> u64 {
-        let rows = self.rows.len();
-        let mut total = 0;
-        for (i, r) in self.rows.iter().enumerate() {
-            let moment = rows - i;
-            total += r.iter().filter(|&&c| c == 'O').count() * moment;
-        }
-
-        total as u64
+fn slide(grid: &mut Grid<char>, dir: Direction) {
+    match dir {
+        Direction::N => grid.par_cols_mut().for_each(|mut c| roll(c.iter_mut())),
+        Direction::S => grid
+            .par_cols_mut()
+            .for_each(|mut c| roll(c.iter_mut().rev())),
+        Direction::W => grid.par_rows_mut().for_each(|mut r| roll(r.iter_mut())),
+        Direction::E => grid
+            .par_rows_mut()
+            .for_each(|mut r| roll(r.iter_mut().rev())),
     }
 }
 
+fn moment(grid: &Grid<char>) -> u64 {
+    let rows = grid.num_rows();
+    grid.coords()
+        .filter(|&c| grid[c] == 'O')
+        .map(|c| (rows - c.row) as u64)
+        .sum()
+}
+
 #[aoc(day14, part1)]
 fn part1(input: &str) -> u64 {
-    let mut grid = Grid::parse(input);
+    let mut grid = Grid::parse_chars(input);
 
-    grid.slide(Direction::N);
-    grid.moment()
+    slide(&mut grid, Direction::N);
+    moment(&grid)
 }
 
 #[aoc(day14, part2)]
 fn part2(input: &str) -> u64 {
-    let mut grid = Grid::parse(input);
-
-    let mut old = Vec::new();
-    old.push(grid.clone());
-    let last;
-
-    loop {
-        grid.slide(Direction::N);
-        grid.slide(Direction::W);
-        grid.slide(Direction::S);
-        grid.slide(Direction::E);
-
-        if old.contains(&grid) {
-            last = grid.clone();
-            break;
-        } else {
-            old.push(grid.clone())
-        }
-    }
-
-    let mut cycle_start = 0;
-
-    while old[0] != last {
-        cycle_start += 1;
-        old.remove(0);
-    }
-
-    let cycle_len = old.len();
-
-    let index = (1_000_000_000 - cycle_start) % cycle_len;
-
-    old[index].moment()
+    let grid = Grid::parse_chars(input);
+
+    let spin = |grid: &Grid<char>| {
+        let mut grid = grid.clone();
+        slide(&mut grid, Direction::N);
+        slide(&mut grid, Direction::W);
+        slide(&mut grid, Direction::S);
+        slide(&mut grid, Direction::E);
+        grid
+    };
+
+    moment(&nth_state(grid, spin, 1_000_000_000))
 }
 
 #[cfg(test)]
@@ -2,15 +2,7 @@ use std::collections::HashSet;
 
 use runner::aoc;
 
-use aoc_lib::{Coord, Grid};
-
-fn parse(input: &str) -> Grid<char> {
-    let mut grid = Grid::build();
-    for l in input.trim().lines().map(str::trim) {
-        grid.push_row(l.chars().collect())
-    }
-    grid.finish()
-}
+use aoc_lib::{packed::BitGrid, Coord, Grid};
 
 fn follow(cache: &mut HashSet<(Coord, Coord)>, grid: &Grid<char>, loc: Coord, dir: Coord) {
     cache.clear();
@@ -68,42 +60,29 @@ fn follow_beam(cache: &mut HashSet<(Coord, Coord)>, grid: &Grid<char>, loc: Coor
     }
 }
 
-fn count(cache: &HashSet<(Coord, Coord)>, grid: &Grid<char>, scratch: &mut HashSet<Coord>) -> i64 {
-    scratch.clear();
-    scratch.extend(cache.iter().map(|p| p.0));
-
-    let mut total = 0;
-
-    let cols = grid.num_cols();
-
-    for c in grid.coords() {
-        if scratch.contains(&c) {
-            total += 1;
-            //print!("#");
-        } else {
-            //print!(".");
-        }
-
-        if c.col == cols - 1 {
-            //println!();
+fn count(cache: &HashSet<(Coord, Coord)>, energised: &mut BitGrid) -> i64 {
+    energised.clear();
+    for &(loc, _) in cache {
+        if energised.contains(loc) {
+            energised.insert(loc);
         }
     }
-    total
+    energised.count() as i64
 }
 
 #[aoc(day16, part1)]
 fn part1(input: &str) -> i64 {
-    let grid = parse(input);
+    let grid = Grid::parse_chars(input);
     let mut cache = HashSet::new();
-    let mut scratch = HashSet::new();
+    let mut energised = BitGrid::new(grid.num_rows() as usize, grid.num_cols() as usize);
     follow_beam(&mut cache, &grid, Coord::new(0, 0), Coord::E);
 
-    count(&cache, &grid, &mut scratch)
+    count(&cache, &mut energised)
 }
 
 #[aoc(day16, part2)]
 fn part2(input: &str) -> i64 {
-    let grid = parse(input);
+    let grid = Grid::parse_chars(input);
     let cols = grid.num_cols();
     let rows = grid.num_rows();
     let iter = (0..cols)
@@ -113,12 +92,12 @@ fn part2(input: &str) -> i64 {
         .chain((0..rows).map(|r| (Coord::new(r, cols - 1), Coord::W)));
 
     let mut cache = HashSet::new();
-    let mut scratch = HashSet::new();
+    let mut energised = BitGrid::new(rows as usize, cols as usize);
 
     let mut out = 0;
     for (start, dir) in iter {
         follow(&mut cache, &grid, start, dir);
-        let c = count(&cache, &grid, &mut scratch);
+        let c = count(&cache, &mut energised);
         out = out.max(c);
     }
     out
@@ -1,208 +1,63 @@
-use std::collections::{BinaryHeap, HashSet};
-
-use aoc_lib::{Coord, Grid};
+use aoc_lib::{search::dijkstra, Coord, Direction, Grid};
 use runner::aoc;
 
-fn parse(input: &str) -> Grid<i64> {
-    let mut grid = Grid::build();
-
-    for l in input.trim().lines().map(str::trim) {
-        grid.push_row(l.chars().map(|c| c.to_string().parse().unwrap()).collect());
-    }
-
-    grid.finish()
-}
-
 #[derive(Hash, PartialEq, Eq, Clone, Copy, Debug)]
 struct State {
     loc: Coord,
     run_len: i64,
-    run_dir: Coord,
-    heat: i64,
-}
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
+    run_dir: Direction,
 }
 
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.heat.cmp(&self.heat)
-    }
-}
-
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-struct CachedState {
-    loc: Coord,
-    run_len: i64,
-    run_dir: Coord,
-}
-
-fn solve1(grid: &Grid<i64>) -> i64 {
-    let mut visited: HashSet<CachedState> = HashSet::new();
-
-    let mut queue = BinaryHeap::new();
-
+// Least heat lost getting to the bottom right, turning only after `min_run` steps in a line and
+// at the latest after `max_run`
+fn solve(grid: &Grid<i64>, min_run: i64, max_run: i64) -> i64 {
     let end = Coord::new(grid.num_rows() - 1, grid.num_cols() - 1);
-    let start = State {
+    let starts = [Direction::E, Direction::S].map(|run_dir| State {
         loc: Coord::ORIGIN,
         run_len: 0,
-        run_dir: Coord::E,
-        heat: 0,
-    };
-
-    queue.push(start);
-
-    while let Some(c) = queue.pop() {
-        let cached_state = CachedState {
-            loc: c.loc,
-            run_len: c.run_len,
-            run_dir: c.run_dir,
-        };
+        run_dir,
+    });
 
-        if !visited.insert(cached_state) {
-            continue;
-        }
-
-        if c.loc == end {
-            return c.heat;
-        }
-
-        let dirs = [
-            c.run_dir.rotate_ccw_90(),
-            c.run_dir.rotate_cw_90(),
-            c.run_dir,
-        ];
-
-        for d in dirs {
+    let successors = |c: &State| {
+        let c = *c;
+        let dirs = [c.run_dir.turn_left(), c.run_dir.turn_right(), c.run_dir];
+        dirs.into_iter().filter_map(move |d| {
             let run_len = if d == c.run_dir {
-                if c.run_len == 3 {
-                    continue;
-                }
                 c.run_len + 1
-            } else {
+            } else if c.run_len >= min_run {
                 1
-            };
-
-            let next_loc = c.loc + d;
-            let next_loss = if let Some(&h) = grid.get(next_loc) {
-                h
             } else {
-                continue;
-            };
-
-            let state = State {
-                loc: next_loc,
-                run_len,
-                run_dir: d,
-                heat: c.heat + next_loss,
+                return None;
             };
-
-            queue.push(state)
-        }
-    }
-
-    panic!()
-}
-
-fn solve2(grid: &Grid<i64>) -> i64 {
-    let mut visited: HashSet<CachedState> = HashSet::new();
-
-    let mut queue = BinaryHeap::new();
-
-    let end = Coord::new(grid.num_rows() - 1, grid.num_cols() - 1);
-    let start = State {
-        loc: Coord::ORIGIN,
-        run_len: 0,
-        run_dir: Coord::E,
-        heat: 0,
-    };
-
-    queue.push(start);
-
-    let start = State {
-        loc: Coord::ORIGIN,
-        run_len: 0,
-        run_dir: Coord::S,
-        heat: 0,
-    };
-
-    queue.push(start);
-
-    while let Some(c) = queue.pop() {
-        let cached_state = CachedState {
-            loc: c.loc,
-            run_len: c.run_len,
-            run_dir: c.run_dir,
-        };
-
-        if !visited.insert(cached_state) {
-            continue;
-        }
-
-        if c.loc == end {
-            if c.run_len >= 4 {
-                return c.heat;
-            } else {
-                continue;
+            if run_len > max_run {
+                return None;
             }
-        }
-
-        let dirs = [
-            c.run_dir.rotate_ccw_90(),
-            c.run_dir.rotate_cw_90(),
-            c.run_dir,
-        ];
-
-        for d in dirs {
-            let run_len = if c.run_len < 4 {
-                if d != c.run_dir {
-                    continue;
-                }
-                c.run_len + 1
-            } else if d == c.run_dir {
-                if c.run_len == 10 {
-                    continue;
-                }
-                c.run_len + 1
-            } else {
-                1
-            };
-
-            let next_loc = c.loc + d;
-            let next_loss = if let Some(&h) = grid.get(next_loc) {
-                h
-            } else {
-                continue;
-            };
 
-            let state = State {
-                loc: next_loc,
+            let loc = c.loc + d.to_coord();
+            let &heat = grid.get(loc)?;
+            let next = State {
+                loc,
                 run_len,
                 run_dir: d,
-                heat: c.heat + next_loss,
             };
+            Some((next, heat))
+        })
+    };
 
-            queue.push(state)
-        }
-    }
-
-    panic!()
+    let goal = |c: &State| c.loc == end && c.run_len >= min_run;
+    dijkstra(starts, successors, goal).unwrap().cost
 }
 
 #[aoc(day17, part1)]
 fn part1(input: &str) -> i64 {
-    let grid = parse(input);
-
-    solve1(&grid)
+    let grid = Grid::parse_digits(input);
+    solve(&grid, 1, 3)
 }
 
 #[aoc(day17, part2)]
 fn part2(input: &str) -> i64 {
-    let grid = parse(input);
-    solve2(&grid)
+    let grid = Grid::parse_digits(input);
+    solve(&grid, 4, 10)
 }
 
 #[cfg(test)]
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use aoc_lib::{grid::AABB, Coord};
+use aoc_lib::{grid::AABB, Coord, Direction};
 use itertools::Itertools;
 use runner::aoc;
 
@@ -18,13 +18,7 @@ fn part1(input: &str) -> i64 {
         let (dir, rest) = c.split_once(' ').unwrap();
         let (dist, _color) = rest.split_once(' ').unwrap();
 
-        let dir = match dir {
-            "U" => Coord::N,
-            "R" => Coord::E,
-            "D" => Coord::S,
-            "L" => Coord::W,
-            _ => panic!(),
-        };
+        let dir = dir.parse::<Direction>().unwrap().to_coord();
 
         let dist: i64 = dist.parse().unwrap();
 
@@ -75,7 +69,7 @@ fn solve(commands: &[(Coord, i64)]) -> i64 {
 
     for v in compressed_vertexes.iter().copied() {
         let dist = v - current;
-        let dir = Coord::new(dist.row.signum(), dist.col.signum());
+        let dir = dist.signum();
 
         while current != v {
             current += dir;
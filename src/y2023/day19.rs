@@ -224,7 +224,7 @@ impl RangeSet {
             .iter()
             .filter(|r| r.contains(range.end))
             .map(|r| r.end)
-            .last()
+            .next_back()
             .unwrap_or(range.end);
 
         let range = Range { start, end };
@@ -314,6 +314,8 @@ struct Range {
     end: i64,
 }
 
+// Overlapping ranges are unordered
+#[allow(clippy::non_canonical_partial_ord_impl)]
 impl PartialOrd for Range {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         if self.end < other.start {
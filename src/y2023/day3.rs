@@ -15,7 +15,7 @@ impl Board {
             let line = line.trim();
             let mut row = Vec::new();
             let mut prev = None;
-            for (i, c) in line.chars().enumerate() {
+            for (i, c) in line.char_indices() {
                 match c {
                     '.' => {
                         row.push(Element::Empty);
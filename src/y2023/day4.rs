@@ -62,7 +62,7 @@ fn part1(input: &str) -> u64 {
 #[aoc(day4, part2)]
 fn part2(input: &str) -> u64 {
     let cards: Vec<_> = input.lines().map(Card::parse).collect();
-    let mut card_amounts: Vec<_> = std::iter::repeat(1u64).take(cards.len()).collect();
+    let mut card_amounts: Vec<_> = std::iter::repeat_n(1u64, cards.len()).collect();
 
     for (i, card) in cards.iter().enumerate() {
         let amount = card_amounts[i];
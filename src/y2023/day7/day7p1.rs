@@ -18,7 +18,7 @@ enum Card {
 
 impl Ord for Card {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        self.to_num().cmp(&other.to_num())
     }
 }
 
@@ -37,7 +37,7 @@ impl Card {
 
 impl PartialOrd for Card {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.to_num().partial_cmp(&other.to_num())
+        Some(self.cmp(other))
     }
 }
 
@@ -102,32 +102,32 @@ impl Hand {
 
 impl PartialOrd for Hand {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
         let t = self.rank();
         let t_o = other.rank();
 
         match t.cmp(&t_o) {
-            Ordering::Less => Some(Ordering::Less),
-            Ordering::Greater => Some(Ordering::Greater),
+            Ordering::Less => Ordering::Less,
+            Ordering::Greater => Ordering::Greater,
             Ordering::Equal => {
                 for (c, c_o) in self.cards.iter().copied().zip(other.cards) {
                     match c.cmp(&c_o) {
-                        Ordering::Less => return Some(Ordering::Less),
-                        Ordering::Greater => return Some(Ordering::Greater),
+                        Ordering::Less => return Ordering::Less,
+                        Ordering::Greater => return Ordering::Greater,
                         Ordering::Equal => {}
                     }
                 }
-                Some(Ordering::Equal)
+                Ordering::Equal
             }
         }
     }
 }
 
-impl Ord for Hand {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
-    }
-}
-
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 enum Type {
     FiveOfAKind,
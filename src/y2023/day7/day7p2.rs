@@ -18,7 +18,7 @@ enum Card {
 
 impl Ord for Card {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        self.to_num().cmp(&other.to_num())
     }
 }
 
@@ -37,7 +37,7 @@ impl Card {
 
 impl PartialOrd for Card {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.to_num().partial_cmp(&other.to_num())
+        Some(self.cmp(other))
     }
 }
 
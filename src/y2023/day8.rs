@@ -129,7 +129,7 @@ fn gcd(a: u64, b: u64) -> u64 {
     let mut result = a.min(b);
 
     while result > 0 {
-        if a % result == 0 && b % result == 0 {
+        if a.is_multiple_of(result) && b.is_multiple_of(result) {
             break;
         }
         result -= 1;